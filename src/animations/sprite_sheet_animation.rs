@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use rand::Rng;
+use serde::Deserialize;
 use std::{collections::HashMap, time::Duration};
 
 use crate::{
@@ -18,11 +19,28 @@ use crate::{
     menu::{ManorLightsPattern, ManorLightsTimer, Smoke, Title, TitleState},
 };
 
-#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect, Component)]
+#[derive(
+    Deserialize,
+    Default,
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Reflect,
+    Component,
+)]
 pub enum CharacterState {
     #[default]
     Idle,
     Run,
+    // -- Reactions, only if declared in the `AnimationIndices` --
+    Talk,
+    Hurt,
+    Attack,
 }
 
 #[derive(Deref, DerefMut, Component)]
@@ -0,0 +1,296 @@
+//! Emotes and reactions
+//!
+//! An emote ("!", "?", ...) pops above the head of a character for a while;
+//! a reaction plays an extra `CharacterState` (`Talk`, `Hurt`, `Attack`)
+//! if its spritesheet declares it (see `npcs::roster::AnimationDefinition`).
+//!
+//! Chases, investigations, dialogs, combats and petting the cat trigger them.
+
+use bevy::prelude::*;
+
+use crate::{
+    animations::sprite_sheet_animation::{AnimationIndices, CharacterState},
+    characters::{
+        npcs::{aggression::EngagePursuitEvent, cat::PetCatEvent, investigation::Investigation},
+        player::Player,
+    },
+    combat::CombatEvent,
+    constants::character::emotes::*,
+    ui::{dialog_scrolls::Monolog, dialog_systems::CurrentInterlocutor},
+    GameState,
+};
+
+pub struct EmotePlugin;
+
+impl Plugin for EmotePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EmoteResources>()
+            .add_event::<EmoteEvent>()
+            .add_event::<ReactionEvent>()
+            .add_systems(
+                Update,
+                (
+                    (
+                        emote_on_chase,
+                        emote_on_investigation,
+                        react_to_combat,
+                        react_to_dialog_lines,
+                        emote_on_pet,
+                    )
+                        .before(spawn_emotes)
+                        .before(play_reactions),
+                    spawn_emotes,
+                    play_reactions,
+                    emote_timeout,
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+#[derive(Resource)]
+pub struct EmoteResources {
+    font: Handle<Font>,
+}
+
+impl FromWorld for EmoteResources {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        EmoteResources {
+            font: asset_server.load("fonts/dpcomic.ttf"),
+        }
+    }
+}
+
+#[derive(Reflect, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Emote {
+    Surprised,
+    Angry,
+    Heart,
+    Question,
+}
+
+impl Emote {
+    fn glyph(&self) -> &'static str {
+        match self {
+            Emote::Surprised => "!",
+            Emote::Angry => "#",
+            Emote::Heart => "<3",
+            Emote::Question => "?",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Emote::Surprised => Color::YELLOW,
+            Emote::Angry => Color::RED,
+            Emote::Heart => Color::PINK,
+            Emote::Question => Color::WHITE,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+/// Child of the character, despawned when the timer is finished.
+#[derive(Component)]
+pub struct EmoteBubble {
+    timer: Timer,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Events                                   */
+/* -------------------------------------------------------------------------- */
+
+/// Happens when:
+///   - characters::emotes::emote_on_chase
+///     - A npc engages a pursuit
+///   - characters::emotes::emote_on_investigation
+///     - A npc starts to investigate
+///   - characters::emotes::react_to_combat
+///     - A combat starts
+///   - characters::emotes::emote_on_pet
+///     - The cat is petted
+///
+/// Read in characters::emotes::spawn_emotes
+///   - replaces the current emote of the character, if any
+#[derive(Event)]
+pub struct EmoteEvent {
+    pub character: Entity,
+    pub emote: Emote,
+}
+
+/// Happens when:
+///   - characters::emotes::react_to_combat
+///     - A combat starts
+///   - characters::emotes::react_to_dialog_lines
+///     - The interlocutor says a new line
+///
+/// Read in characters::emotes::play_reactions
+///   - plays the state, if declared in the `AnimationIndices` of the character
+#[derive(Event)]
+pub struct ReactionEvent {
+    pub character: Entity,
+    pub state: CharacterState,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+pub fn spawn_emotes(
+    mut commands: Commands,
+    mut ev_emote: EventReader<EmoteEvent>,
+    emote_resources: Res<EmoteResources>,
+
+    character_query: Query<Option<&Children>>,
+    bubble_query: Query<(), With<EmoteBubble>>,
+) {
+    for EmoteEvent { character, emote } in ev_emote.iter() {
+        let Ok(potential_children) = character_query.get(*character) else {
+            continue;
+        };
+        if let Some(children) = potential_children {
+            for child in children {
+                if bubble_query.get(*child).is_ok() {
+                    commands.entity(*child).despawn_recursive();
+                }
+            }
+        }
+
+        let bubble = commands
+            .spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        emote.glyph(),
+                        TextStyle {
+                            font: emote_resources.font.clone(),
+                            font_size: EMOTE_FONT_SIZE,
+                            color: emote.color(),
+                        },
+                    ),
+                    transform: Transform::from_translation(EMOTE_TRANSLATION.into()),
+                    ..default()
+                },
+                EmoteBubble {
+                    timer: Timer::from_seconds(EMOTE_DURATION, TimerMode::Once),
+                },
+                Name::new(format!("Emote {emote:?}")),
+            ))
+            .id();
+
+        commands.entity(*character).add_child(bubble);
+    }
+}
+
+pub fn emote_timeout(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut bubble_query: Query<(Entity, &mut EmoteBubble)>,
+) {
+    for (bubble, mut emote_bubble) in &mut bubble_query {
+        emote_bubble.timer.tick(time.delta());
+        if emote_bubble.timer.finished() {
+            commands.entity(bubble).despawn_recursive();
+        }
+    }
+}
+
+/// A running character is not interrupted.
+pub fn play_reactions(
+    mut ev_reaction: EventReader<ReactionEvent>,
+    mut character_query: Query<(&AnimationIndices, &mut CharacterState)>,
+) {
+    for ReactionEvent { character, state } in ev_reaction.iter() {
+        if let Ok((indices, mut character_state)) = character_query.get_mut(*character) {
+            if indices.contains_key(state) && *character_state != CharacterState::Run {
+                *character_state = *state;
+            }
+        }
+    }
+}
+
+pub fn emote_on_chase(
+    mut ev_engage_pursuit: EventReader<EngagePursuitEvent>,
+    mut ev_emote: EventWriter<EmoteEvent>,
+) {
+    for EngagePursuitEvent { npc_entity, .. } in ev_engage_pursuit.iter() {
+        ev_emote.send(EmoteEvent {
+            character: *npc_entity,
+            emote: Emote::Surprised,
+        });
+    }
+}
+
+pub fn emote_on_investigation(
+    investigation_query: Query<Entity, Added<Investigation>>,
+    mut ev_emote: EventWriter<EmoteEvent>,
+) {
+    for npc in &investigation_query {
+        ev_emote.send(EmoteEvent {
+            character: npc,
+            emote: Emote::Question,
+        });
+    }
+}
+
+/// The foe attacks, the player is hurt.
+pub fn react_to_combat(
+    mut ev_combat: EventReader<CombatEvent>,
+    player_query: Query<Entity, With<Player>>,
+
+    mut ev_emote: EventWriter<EmoteEvent>,
+    mut ev_reaction: EventWriter<ReactionEvent>,
+) {
+    for CombatEvent { entity } in ev_combat.iter() {
+        ev_emote.send(EmoteEvent {
+            character: *entity,
+            emote: Emote::Angry,
+        });
+        ev_reaction.send(ReactionEvent {
+            character: *entity,
+            state: CharacterState::Attack,
+        });
+        if let Ok(player) = player_query.get_single() {
+            ev_reaction.send(ReactionEvent {
+                character: player,
+                state: CharacterState::Hurt,
+            });
+        }
+    }
+}
+
+/// The interlocutor talks at each new line of its monolog.
+pub fn react_to_dialog_lines(
+    monolog: Res<Monolog>,
+    current_interlocutor: Res<CurrentInterlocutor>,
+    player_query: Query<Entity, With<Player>>,
+
+    mut ev_reaction: EventWriter<ReactionEvent>,
+) {
+    if !monolog.is_changed() || monolog.texts.is_empty() {
+        return;
+    }
+    if let Some(interlocutor) = current_interlocutor.interlocutor {
+        if player_query.get(interlocutor).is_err() {
+            ev_reaction.send(ReactionEvent {
+                character: interlocutor,
+                state: CharacterState::Talk,
+            });
+        }
+    }
+}
+
+pub fn emote_on_pet(
+    mut ev_pet_cat: EventReader<PetCatEvent>,
+    mut ev_emote: EventWriter<EmoteEvent>,
+) {
+    for PetCatEvent(cat) in ev_pet_cat.iter() {
+        ev_emote.send(EmoteEvent {
+            character: *cat,
+            emote: Emote::Heart,
+        });
+    }
+}
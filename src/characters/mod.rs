@@ -1,6 +1,8 @@
+pub mod emotes;
 pub mod movement;
 pub mod npcs;
 pub mod player;
+pub mod stamina;
 
 use bevy::prelude::*;
 
@@ -8,7 +10,12 @@ pub struct CharactersPlugin;
 
 impl Plugin for CharactersPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((player::PlayerPlugin, npcs::NPCPlugin));
+        app.add_plugins((
+            player::PlayerPlugin,
+            npcs::NPCPlugin,
+            emotes::EmotePlugin,
+            stamina::StaminaPlugin,
+        ));
     }
 }
 
@@ -5,10 +5,10 @@ use bevy_rapier2d::prelude::*;
 use crate::{
     characters::npcs::{
         movement::{Chaser, NPCBehavior, TargetSeeker},
+        profile::AIProfile,
         NPC,
     },
     combat::FairPlayTimer,
-    constants::character::npcs::movement::EVASION_TIMER,
 };
 
 /* -------------------------------------------------------------------------- */
@@ -30,19 +30,28 @@ pub struct PursuitRangeSensor;
 ///     - target is not found/exist
 ///     - target is reach
 ///
-/// Read in npc::aggression::deactivate_pursuit_urge
+/// Read in
+///   - npc::aggression::deactivate_pursuit_urge
+///   - npc::investigation::investigate_lost_target
+///     - search the target, if not reached
 #[derive(Event)]
 pub struct StopChaseEvent {
     pub npc_entity: Entity,
 }
 
 /// Happens when:
-///   - npcs::movement::sensor_management
-///     - An npc detected an enemy
+///   - npc::perception::engage_perceived_target
+///     - An npc saw or heard its target
+///   - npc::suspicion::suspicion_update
+///     - An npc is sure about its target
+///   - npc::alert::answer_alert
+///     - An ally, close enough, started a chase
 ///
 /// Read in
 ///   - npc::aggression::activate_pursuit_urge
 ///     - change the NPCBehavior to chase the target
+///   - npc::alert::propagate_alert
+///     - warn the allies around
 #[derive(Event)]
 pub struct EngagePursuitEvent {
     pub npc_entity: Entity,
@@ -90,7 +99,10 @@ pub fn activate_pursuit_urge(
         target_entity,
     } in ev_engage_pursuit.iter()
     {
-        let (npc, children, name) = npc_query.get(*npc_entity).unwrap();
+        // The npc might have been despawned since
+        let Ok((npc, children, name)) = npc_query.get(*npc_entity) else {
+            continue;
+        };
         info!("activate pursuit urge to {}", name);
 
         commands.entity(npc).insert(Chaser::new(*target_entity));
@@ -121,6 +133,7 @@ pub fn deactivate_pursuit_urge(
         (Entity, &Children, &Name),
         (With<NPC>, With<TargetSeeker>, With<NPCBehavior>),
     >,
+    profile_query: Query<&AIProfile>,
     pursuit_sensor_query: Query<
         Entity,
         (
@@ -141,12 +154,17 @@ pub fn deactivate_pursuit_urge(
     >,
 ) {
     for StopChaseEvent { npc_entity } in ev_stop_chase.iter() {
-        let (npc, children, name) = npc_query.get(*npc_entity).unwrap();
+        // The npc might have been despawned since
+        let Ok((npc, children, name)) = npc_query.get(*npc_entity) else {
+            continue;
+        };
         info!("deactivate pursuit urge to {}", name);
 
         commands
             .entity(npc)
-            .insert(FairPlayTimer::new(EVASION_TIMER));
+            .insert(FairPlayTimer::new(AIProfile::fair_play_time_of(
+                profile_query.get(npc).ok(),
+            )));
 
         commands.entity(npc).remove::<Chaser>();
 
@@ -0,0 +1,198 @@
+//! Alert between allies
+//!
+//! A npc starting a chase warns its allies (same team, same room) around it.
+//!
+//! - the close `TargetSeeker`s join the chase
+//! - the others start an `Investigation` at the last known position of the target
+//!
+//! The alert level slowly decays; while alerted, a npc gets suspicious faster.
+
+use bevy::prelude::*;
+
+use crate::{
+    characters::npcs::{
+        aggression::EngagePursuitEvent,
+        flee::Fleer,
+        idle::RestTime,
+        investigation::Investigation,
+        movement::{Chaser, NPCBehavior, TargetSeeker},
+        NPC,
+    },
+    combat::{FairPlayTimer, InCombat, Reputation},
+    constants::character::npcs::alert::*,
+    locations::temple::Location,
+};
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, Copy, Reflect, Component)]
+pub struct Alert {
+    /// From 1. (just warned) to 0. (removed)
+    pub level: f32,
+    pub target: Entity,
+    /// Where the target was when the alert was given.
+    pub last_known_position: Vec2,
+}
+
+impl Alert {
+    pub fn new(target: Entity, last_known_position: Vec2) -> Self {
+        Alert {
+            level: 1.,
+            target,
+            last_known_position,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Events                                   */
+/* -------------------------------------------------------------------------- */
+
+/// Happens when:
+///   - npc::alert::propagate_alert
+///     - An ally, close enough, started a chase
+///
+/// Read in npc::alert::answer_alert
+#[derive(Event)]
+pub struct AlertEvent {
+    pub npc_entity: Entity,
+    pub target_entity: Entity,
+    pub last_known_position: Vec2,
+    /// Close enough to directly join the chase.
+    pub join_chase: bool,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+/// The npcs engaging a pursuit warn their allies.
+///
+/// An alerted ally joining the chase doesn't warn the others in turn.
+pub fn propagate_alert(
+    mut commands: Commands,
+    mut ev_engage_pursuit: EventReader<EngagePursuitEvent>,
+
+    sender_query: Query<(&Reputation, &Location, &Transform, &Name), (With<NPC>, Without<Alert>)>,
+    ally_query: Query<
+        (
+            Entity,
+            &Reputation,
+            &Location,
+            &Transform,
+            Option<&TargetSeeker>,
+            Option<&FairPlayTimer>,
+        ),
+        (
+            With<NPC>,
+            Without<Alert>,
+            Without<Chaser>,
+            Without<Fleer>,
+            Without<InCombat>,
+        ),
+    >,
+    target_query: Query<&GlobalTransform>,
+
+    mut ev_alert: EventWriter<AlertEvent>,
+) {
+    let mut warned_allies = Vec::new();
+    for EngagePursuitEvent {
+        npc_entity,
+        target_entity,
+    } in ev_engage_pursuit.iter()
+    {
+        let (Ok((reputation, location, transform, name)), Ok(target_transform)) = (
+            sender_query.get(*npc_entity),
+            target_query.get(*target_entity),
+        ) else {
+            continue;
+        };
+        let last_known_position = target_transform.translation().truncate();
+
+        commands
+            .entity(*npc_entity)
+            .insert(Alert::new(*target_entity, last_known_position));
+
+        for (
+            ally,
+            ally_reputation,
+            ally_location,
+            ally_transform,
+            potential_target_seeker,
+            potential_fair_play_timer,
+        ) in &ally_query
+        {
+            if ally == *npc_entity
+                || warned_allies.contains(&ally)
+                || ally_location != location
+                || !reputation.in_the_same_team(ally_reputation)
+            {
+                continue;
+            }
+            let distance = ally_transform.translation.distance(transform.translation);
+            if distance > ALERT_RADIUS {
+                continue;
+            }
+            warned_allies.push(ally);
+            info!("{} alerts an ally", name);
+
+            ev_alert.send(AlertEvent {
+                npc_entity: ally,
+                target_entity: *target_entity,
+                last_known_position,
+                join_chase: potential_target_seeker.is_some()
+                    && potential_fair_play_timer.is_none()
+                    && distance <= ALERT_JOIN_RADIUS,
+            });
+        }
+    }
+}
+
+/// The alerted npc stops resting, then joins the chase or investigates.
+pub fn answer_alert(
+    mut commands: Commands,
+    mut ev_alert: EventReader<AlertEvent>,
+
+    behavior_query: Query<&NPCBehavior>,
+
+    mut ev_engage_pursuit: EventWriter<EngagePursuitEvent>,
+) {
+    for AlertEvent {
+        npc_entity,
+        target_entity,
+        last_known_position,
+        join_chase,
+    } in ev_alert.iter()
+    {
+        commands
+            .entity(*npc_entity)
+            .remove::<RestTime>()
+            .insert(Alert::new(*target_entity, *last_known_position));
+
+        if *join_chase {
+            ev_engage_pursuit.send(EngagePursuitEvent {
+                npc_entity: *npc_entity,
+                target_entity: *target_entity,
+            });
+        } else if let Ok(behavior) = behavior_query.get(*npc_entity) {
+            commands
+                .entity(*npc_entity)
+                .insert(Investigation::new(*last_known_position, *behavior));
+        }
+    }
+}
+
+pub fn alert_decay(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut alert_query: Query<(Entity, &mut Alert)>,
+) {
+    for (npc, mut alert) in &mut alert_query {
+        alert.level -= ALERT_DECAY_RATE * time.delta_seconds();
+        if alert.level <= 0. {
+            commands.entity(npc).remove::<Alert>();
+        }
+    }
+}
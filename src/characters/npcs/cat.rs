@@ -0,0 +1,394 @@
+//! Olf Cat
+//!
+//! The cat lives its own life, apart from the `NPCBehavior`s:
+//!
+//! - wanders around
+//! - naps on its favourite landmarks (`LandmarkTag::CatNap`)
+//! - sometimes sits on the throne (`LandmarkTag::Throne`),
+//!   slipping through the passages if the throne is in another room
+//! - follows the player for a while after being petted
+//! - flees from anything moving too fast around it
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::{Sensor, Velocity};
+use rand::Rng;
+
+use crate::{
+    characters::{
+        movement::Speed,
+        npcs::{OlfCat, NPC},
+        player::Player,
+    },
+    constants::character::npcs::cat::*,
+    locations::{
+        landmarks::{
+            reserve_free_landmark, Direction, Landmark, LandmarkRequest, LandmarkStatus,
+            LandmarkTag,
+        },
+        temple::{Location, LocationSensor},
+    },
+};
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum CatState {
+    /// Walks to this spot, then waits a bit.
+    Wander(Vec2),
+    /// Goes to this landmark and sleeps there.
+    Nap(Entity),
+    /// Goes to the throne landmark and sits there.
+    SitOnThrone(Entity),
+    /// Follows the player who petted it.
+    Follow(Entity),
+    /// Runs away from something too fast, until far enough (or for a while).
+    Flee(Entity),
+}
+
+#[derive(Reflect, Component)]
+pub struct CatBehavior {
+    pub state: CatState,
+    /// Reached the spot/landmark of the current state.
+    pub arrived: bool,
+    /// Duration of the current state.
+    timer: Timer,
+}
+
+impl CatBehavior {
+    pub fn new(state: CatState) -> Self {
+        let duration = match state {
+            CatState::Wander(_) => CAT_WANDER_DURATION,
+            CatState::Nap(_) => CAT_NAP_DURATION,
+            CatState::SitOnThrone(_) => CAT_THRONE_DURATION,
+            CatState::Follow(_) => CAT_FOLLOW_DURATION,
+            CatState::Flee(_) => CAT_FLEE_DURATION,
+        };
+        CatBehavior {
+            state,
+            arrived: false,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+
+    /// The landmark reserved, or occupied, for the current state.
+    fn landmark(&self) -> Option<Entity> {
+        match self.state {
+            CatState::Nap(landmark) | CatState::SitOnThrone(landmark) => Some(landmark),
+            _ => None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Events                                   */
+/* -------------------------------------------------------------------------- */
+
+/// Happens when:
+///   - interactions::interaction
+///     - The player interacts with the cat
+///
+/// Read in npc::cat::pet_cat
+#[derive(Event)]
+pub struct PetCatEvent(pub Entity);
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+/// A petted cat follows the player.
+pub fn pet_cat(
+    mut commands: Commands,
+    mut ev_pet_cat: EventReader<PetCatEvent>,
+
+    mut cat_query: Query<(&mut CatBehavior, &Name), With<OlfCat>>,
+    player_query: Query<Entity, With<Player>>,
+    mut landmark_sensor_query: Query<(Entity, &mut Landmark), With<Sensor>>,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    for PetCatEvent(cat) in ev_pet_cat.iter() {
+        if let Ok((mut behavior, name)) = cat_query.get_mut(*cat) {
+            info!("{} purrs", name);
+            change_state(
+                &mut commands,
+                *cat,
+                &mut behavior,
+                CatState::Follow(player),
+                &mut landmark_sensor_query,
+            );
+        }
+    }
+}
+
+/// Anyone moving too fast around the cat scares it,
+/// except the player it follows.
+pub fn startle_cat(
+    mut commands: Commands,
+
+    mut cat_query: Query<(Entity, &mut CatBehavior, &Transform, &Location, &Name), With<OlfCat>>,
+    mover_query: Query<
+        (Entity, &Transform, &Velocity, &Location),
+        (Or<(With<Player>, With<NPC>)>, Without<OlfCat>),
+    >,
+    mut landmark_sensor_query: Query<(Entity, &mut Landmark), With<Sensor>>,
+) {
+    for (cat, mut behavior, transform, location, name) in &mut cat_query {
+        let followed = match behavior.state {
+            CatState::Flee(_) => continue,
+            CatState::Follow(target) => Some(target),
+            _ => None,
+        };
+        let position = transform.translation.truncate();
+
+        let threat = mover_query
+            .iter()
+            .find(|(mover, mover_transform, rb_vel, mover_location)| {
+                Some(*mover) != followed
+                    && *mover_location == location
+                    && rb_vel.linvel.length() >= CAT_STARTLE_SPEED
+                    && mover_transform.translation.truncate().distance(position)
+                        <= CAT_STARTLE_RADIUS
+            });
+        if let Some((threat, ..)) = threat {
+            info!("{} is startled", name);
+            change_state(
+                &mut commands,
+                cat,
+                &mut behavior,
+                CatState::Flee(threat),
+                &mut landmark_sensor_query,
+            );
+        }
+    }
+}
+
+/// Picks the next activity when the current one is over.
+pub fn cat_brain(
+    mut commands: Commands,
+    time: Res<Time>,
+
+    mut cat_query: Query<(Entity, &mut CatBehavior, &Transform, &Location), With<OlfCat>>,
+    pos_query: Query<&GlobalTransform>,
+    mut landmark_sensor_query: Query<(Entity, &mut Landmark), With<Sensor>>,
+) {
+    for (cat, mut behavior, transform, location) in &mut cat_query {
+        let position = transform.translation.truncate();
+
+        if behavior.arrived
+            || matches!(
+                behavior.state,
+                CatState::Wander(_) | CatState::Follow(_) | CatState::Flee(_)
+            )
+        {
+            behavior.timer.tick(time.delta());
+        }
+
+        let over = match behavior.state {
+            CatState::Flee(threat) => match pos_query.get(threat) {
+                Ok(threat_transform) => {
+                    threat_transform.translation().truncate().distance(position)
+                        >= CAT_SAFE_DISTANCE
+                        || behavior.timer.finished()
+                }
+                Err(_) => true,
+            },
+            CatState::Follow(target) => pos_query.get(target).is_err() || behavior.timer.finished(),
+            CatState::Nap(landmark) | CatState::SitOnThrone(landmark) => {
+                // e.g. the player took it
+                let taken = match landmark_sensor_query.get(landmark) {
+                    Ok((_, landmark)) => match landmark.status {
+                        LandmarkStatus::Free => false,
+                        LandmarkStatus::ReservedBy(owner) | LandmarkStatus::OccupiedBy(owner) => {
+                            owner != cat
+                        }
+                    },
+                    Err(_) => true,
+                };
+                taken || behavior.timer.finished()
+            }
+            CatState::Wander(_) => behavior.timer.finished(),
+        };
+
+        if over {
+            let next_state = next_activity(cat, position, *location, &mut landmark_sensor_query);
+            change_state(
+                &mut commands,
+                cat,
+                &mut behavior,
+                next_state,
+                &mut landmark_sensor_query,
+            );
+        }
+    }
+}
+
+/// Cat-like movement: walks lazily, runs away fast.
+pub fn cat_movement(
+    mut commands: Commands,
+
+    mut cat_query: Query<
+        (
+            Entity,
+            &mut CatBehavior,
+            &Transform,
+            &mut Location,
+            &Speed,
+            &mut Velocity,
+        ),
+        With<OlfCat>,
+    >,
+    mut landmark_sensor_query: Query<(Entity, &mut Landmark), With<Sensor>>,
+    pos_query: Query<&GlobalTransform>,
+    passage_query: Query<(&LocationSensor, &GlobalTransform)>,
+    location_query: Query<&Location, Without<OlfCat>>,
+) {
+    for (cat, mut behavior, transform, mut location, speed, mut rb_vel) in &mut cat_query {
+        let position = transform.translation.truncate();
+        let towards = |destination: Vec2, factor: f32| {
+            if position.distance(destination) <= CAT_ARRIVAL_DISTANCE {
+                Vec2::ZERO
+            } else {
+                (destination - position).normalize() * **speed * factor
+            }
+        };
+
+        let velocity = match behavior.state {
+            CatState::Wander(spot) => {
+                let velocity = towards(spot, CAT_WALK_FACTOR);
+                behavior.arrived = velocity == Vec2::ZERO;
+                velocity
+            }
+            CatState::Nap(landmark_entity) | CatState::SitOnThrone(landmark_entity) => {
+                let (Ok((_, mut landmark)), Ok(landmark_transform)) = (
+                    landmark_sensor_query.get_mut(landmark_entity),
+                    pos_query.get(landmark_entity),
+                ) else {
+                    rb_vel.linvel = Vec2::ZERO;
+                    continue;
+                };
+
+                if landmark.location != *location {
+                    // Slips through the passage leading to the landmark's room
+                    let passage = passage_query
+                        .iter()
+                        .filter(|(sensor, _)| sensor.location == landmark.location)
+                        .map(|(_, passage_transform)| passage_transform.translation().truncate())
+                        .min_by(|a, b| a.distance(position).total_cmp(&b.distance(position)));
+                    match passage {
+                        None => Vec2::ZERO,
+                        Some(passage) => {
+                            let velocity = towards(passage, CAT_WALK_FACTOR);
+                            if velocity == Vec2::ZERO {
+                                *location = landmark.location;
+                            }
+                            velocity
+                        }
+                    }
+                } else if behavior.arrived {
+                    Vec2::ZERO
+                } else {
+                    let velocity =
+                        towards(landmark_transform.translation().truncate(), CAT_WALK_FACTOR);
+                    if velocity == Vec2::ZERO {
+                        behavior.arrived = true;
+                        landmark.status = LandmarkStatus::OccupiedBy(cat);
+                        if let Some(direction) = landmark.direction {
+                            commands.entity(cat).insert(direction);
+                        }
+                    }
+                    velocity
+                }
+            }
+            CatState::Follow(target) => {
+                let (Ok(target_transform), Ok(target_location)) =
+                    (pos_query.get(target), location_query.get(target))
+                else {
+                    rb_vel.linvel = Vec2::ZERO;
+                    continue;
+                };
+                if *location != *target_location {
+                    *location = *target_location;
+                }
+                let target_position = target_transform.translation().truncate();
+                if position.distance(target_position) <= CAT_FOLLOW_DISTANCE {
+                    Vec2::ZERO
+                } else {
+                    towards(target_position, CAT_FOLLOW_FACTOR)
+                }
+            }
+            CatState::Flee(threat) => match pos_query.get(threat) {
+                Err(_) => Vec2::ZERO,
+                Ok(threat_transform) => {
+                    let away = position - threat_transform.translation().truncate();
+                    away.try_normalize().unwrap_or(Vec2::X) * **speed * CAT_FLEE_FACTOR
+                }
+            },
+        };
+        rb_vel.linvel = velocity;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Helpers                                  */
+/* -------------------------------------------------------------------------- */
+
+/// Leaves the landmark of the previous state (if any) for the new one.
+fn change_state(
+    commands: &mut Commands,
+    cat: Entity,
+    behavior: &mut CatBehavior,
+    state: CatState,
+    landmark_sensor_query: &mut Query<(Entity, &mut Landmark), With<Sensor>>,
+) {
+    if let Some(landmark_entity) = behavior.landmark() {
+        if let Ok((_, mut landmark)) = landmark_sensor_query.get_mut(landmark_entity) {
+            if matches!(
+                landmark.status,
+                LandmarkStatus::ReservedBy(owner) | LandmarkStatus::OccupiedBy(owner) if owner == cat
+            ) {
+                landmark.status = LandmarkStatus::Free;
+            }
+        }
+        commands.entity(cat).remove::<Direction>();
+    }
+    *behavior = CatBehavior::new(state);
+}
+
+/// A nap, the throne or a stroll.
+fn next_activity(
+    cat: Entity,
+    position: Vec2,
+    location: Location,
+    landmark_sensor_query: &mut Query<(Entity, &mut Landmark), With<Sensor>>,
+) -> CatState {
+    let mut rng = rand::thread_rng();
+    let roll: f64 = rng.gen();
+
+    if roll < CAT_THRONE_CHANCE {
+        // The throne is in the temple
+        if let Ok(throne) = reserve_free_landmark(
+            landmark_sensor_query,
+            Location::Temple,
+            &LandmarkRequest::Tagged(LandmarkTag::Throne),
+            cat,
+        ) {
+            return CatState::SitOnThrone(throne);
+        }
+    } else if roll < CAT_THRONE_CHANCE + CAT_NAP_CHANCE {
+        if let Ok(spot) = reserve_free_landmark(
+            landmark_sensor_query,
+            location,
+            &LandmarkRequest::Tagged(LandmarkTag::CatNap),
+            cat,
+        ) {
+            return CatState::Nap(spot);
+        }
+    }
+
+    let angle = rng.gen_range(0. ..std::f32::consts::TAU);
+    let distance = rng.gen_range(0. ..CAT_WANDER_RADIUS);
+    CatState::Wander(position + Vec2::from_angle(angle) * distance)
+}
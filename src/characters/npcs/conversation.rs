@@ -0,0 +1,429 @@
+//! Conversations between npcs
+//!
+//! When all landmarks of a `LandmarkGroup` are occupied by npcs,
+//! they talk together: each line is displayed in a speech bubble
+//! above the speaker's head.
+//!
+//! Each `source` of the dialog tree is a role given to a distinct member:
+//! the member whose `Speaker` bears this name if any, otherwise any other member.
+//!
+//! The player can join by interacting with any member.
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Sensor;
+use rand::seq::{IteratorRandom, SliceRandom};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::Path,
+};
+use yml_dialog::{Content, DialogNode};
+
+use crate::{
+    characters::{
+        npcs::{flee::Fleer, idle::RestTime, movement::Chaser, NPC},
+        player::Player,
+    },
+    combat::InCombat,
+    constants::character::conversation::*,
+    locations::landmarks::{Direction, Landmark, LandmarkGroup, LandmarkStatus},
+};
+
+use super::CharacterInteractionEvent;
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+/// Name used as `source` in the dialog files.
+#[derive(Debug, Deref, Clone, Component)]
+pub struct Speaker(pub String);
+
+/// Placed on the `LandmarkGroup` where the conversation happens.
+#[derive(Component)]
+pub struct Conversation {
+    pub members: Vec<Entity>,
+    /// The member saying the lines of each `source`.
+    pub roles: BTreeMap<String, Entity>,
+    dialog_tree: BTreeMap<usize, DialogNode>,
+    current_state: usize,
+    current_line: usize,
+    /// The bubble of the current line, if any.
+    bubble: Option<Entity>,
+    line_timer: Timer,
+}
+
+/// Points to the `LandmarkGroup` of the conversation.
+#[derive(Deref, Component)]
+pub struct InConversation(pub Entity);
+
+#[derive(Component)]
+pub struct SpeechBubble;
+
+/* -------------------------------------------------------------------------- */
+/*                                  Resources                                 */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Resource)]
+pub struct ConversationResources {
+    dialog_trees: Vec<BTreeMap<usize, DialogNode>>,
+    font: Handle<Font>,
+}
+
+#[derive(Debug)]
+pub enum ConversationsError {
+    Io(std::io::Error),
+    Parse(serde_yaml::Error),
+}
+
+fn load_conversations_file(
+    path: &Path,
+) -> Result<Vec<BTreeMap<usize, DialogNode>>, ConversationsError> {
+    let conversations_file = fs::File::open(path).map_err(ConversationsError::Io)?;
+    serde_yaml::from_reader(conversations_file).map_err(ConversationsError::Parse)
+}
+
+/// Without a valid conversations file, npcs gathered just stand there.
+pub fn load_conversations(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let dialog_trees = match load_conversations_file(Path::new(NPC_CONVERSATIONS_PATH)) {
+        Ok(dialog_trees) => dialog_trees,
+        Err(error) => {
+            error!("Invalid npc conversations {NPC_CONVERSATIONS_PATH}: {error:?}");
+            Vec::new()
+        }
+    };
+
+    commands.insert_resource(ConversationResources {
+        dialog_trees,
+        font: asset_server.load("fonts/dpcomic.ttf"),
+    });
+}
+
+/// All roles speaking in this dialog tree.
+fn speakers(dialog_tree: &BTreeMap<usize, DialogNode>) -> HashSet<String> {
+    dialog_tree
+        .values()
+        .filter(|node| matches!(node.content(), Content::Monolog { .. }))
+        .map(|node| node.source().to_string())
+        .filter(|source| source != "Player")
+        .collect()
+}
+
+/// Gives each role of the dialog tree to a distinct member,
+/// if there are enough members.
+fn cast_roles(
+    dialog_tree: &BTreeMap<usize, DialogNode>,
+    members: &[(Entity, &Speaker)],
+) -> Option<BTreeMap<String, Entity>> {
+    let roles = speakers(dialog_tree);
+    let (named, unnamed): (Vec<_>, Vec<_>) = roles
+        .into_iter()
+        .partition(|role| members.iter().any(|(_, speaker)| speaker.0 == *role));
+
+    let mut cast = BTreeMap::new();
+    for role in named {
+        let (member, _) = members.iter().find(|(_, speaker)| speaker.0 == role)?;
+        cast.insert(role, *member);
+    }
+    let mut extras: Vec<Entity> = members
+        .iter()
+        .map(|(member, _)| *member)
+        .filter(|member| !cast.values().any(|cast_member| cast_member == member))
+        .collect();
+    for role in unnamed {
+        cast.insert(role, extras.pop()?);
+    }
+    Some(cast)
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+/// Starts a conversation when all landmarks of a group are occupied by npcs
+/// and a dialog tree has no more roles than them.
+pub fn start_conversation(
+    mut commands: Commands,
+    conversation_resources: Res<ConversationResources>,
+
+    group_query: Query<(Entity, &Children, &Name), (With<LandmarkGroup>, Without<Conversation>)>,
+    landmark_sensor_query: Query<&Landmark, With<Sensor>>,
+    npc_query: Query<
+        (&Speaker, &Transform),
+        (
+            With<NPC>,
+            Without<InConversation>,
+            Without<Chaser>,
+            Without<Fleer>,
+            Without<InCombat>,
+        ),
+    >,
+) {
+    for (group, landmarks, group_name) in &group_query {
+        if landmarks.len() < 2 {
+            continue;
+        }
+
+        let mut members = Vec::new();
+        for landmark in landmarks {
+            match landmark_sensor_query.get(*landmark) {
+                Ok(Landmark {
+                    status: LandmarkStatus::OccupiedBy(occupant),
+                    ..
+                }) if npc_query.get(*occupant).is_ok() => {
+                    members.push((*occupant, *landmark));
+                }
+                _ => break,
+            }
+        }
+        if members.len() != landmarks.len() {
+            continue;
+        }
+
+        let present_speakers: Vec<(Entity, &Speaker)> = members
+            .iter()
+            .filter_map(|(member, _)| {
+                npc_query
+                    .get(*member)
+                    .ok()
+                    .map(|(speaker, _)| (*member, speaker))
+            })
+            .collect();
+        let Some((dialog_tree, roles)) = conversation_resources
+            .dialog_trees
+            .iter()
+            .filter_map(|dialog_tree| {
+                cast_roles(dialog_tree, &present_speakers).map(|roles| (dialog_tree, roles))
+            })
+            .choose(&mut rand::thread_rng())
+        else {
+            continue;
+        };
+        let Some(first_state) = dialog_tree.keys().next() else {
+            continue;
+        };
+
+        info!("Conversation starts at {}", group_name);
+
+        // Face each other
+        for (member, landmark) in &members {
+            let (_, transform) = npc_query.get(*member).unwrap();
+            let direction = match landmark_sensor_query.get(*landmark).unwrap().direction {
+                Some(direction) => direction,
+                None => {
+                    let others_x = members
+                        .iter()
+                        .filter(|(other, _)| other != member)
+                        .map(|(other, _)| npc_query.get(*other).unwrap().1.translation.x)
+                        .sum::<f32>()
+                        / (members.len() - 1) as f32;
+                    if others_x < transform.translation.x {
+                        Direction::Left
+                    } else {
+                        Direction::Right
+                    }
+                }
+            };
+            commands
+                .entity(*member)
+                .insert((InConversation(group), direction));
+        }
+
+        commands.entity(group).insert(Conversation {
+            members: members.iter().map(|(member, _)| *member).collect(),
+            roles,
+            dialog_tree: dialog_tree.clone(),
+            current_state: *first_state,
+            current_line: 0,
+            bubble: None,
+            line_timer: Timer::from_seconds(CONVERSATION_LINE_DELAY, TimerMode::Once),
+        });
+    }
+}
+
+/// Displays the next line when the previous one has been read.
+///
+/// The conversation ends when a member leaves its landmark
+/// or when there is nothing more to say.
+pub fn conversation_progress(
+    mut commands: Commands,
+    time: Res<Time>,
+    conversation_resources: Res<ConversationResources>,
+
+    mut group_query: Query<(Entity, &Children, &mut Conversation)>,
+    landmark_sensor_query: Query<&Landmark, With<Sensor>>,
+    speaker_query: Query<&Speaker>,
+) {
+    for (group, landmarks, mut conversation) in &mut group_query {
+        let conversation = &mut *conversation;
+        let members_still_there = landmarks.iter().all(|landmark| {
+            matches!(
+                landmark_sensor_query.get(*landmark),
+                Ok(Landmark {
+                    status: LandmarkStatus::OccupiedBy(occupant),
+                    ..
+                }) if conversation.members.contains(occupant)
+                    && speaker_query.get(*occupant).is_ok()
+            )
+        });
+        if !members_still_there {
+            end_conversation(&mut commands, group, conversation);
+            continue;
+        }
+
+        conversation.line_timer.tick(time.delta());
+        if conversation.bubble.is_some() && !conversation.line_timer.finished() {
+            continue;
+        }
+
+        if let Some(bubble) = conversation.bubble.take() {
+            commands.entity(bubble).despawn_recursive();
+        }
+
+        // Dive until the next line to say
+        let mut next_line = None;
+        for _ in 0..=conversation.dialog_tree.len() {
+            let Some(current_node) = conversation.dialog_tree.get(&conversation.current_state)
+            else {
+                break;
+            };
+            match current_node.content() {
+                Content::Monolog { text, exit_state } => {
+                    if conversation.current_line < text.len() {
+                        next_line = Some((
+                            current_node.source().to_string(),
+                            text[conversation.current_line].clone(),
+                        ));
+                        conversation.current_line += 1;
+                        break;
+                    }
+                    conversation.current_state = *exit_state;
+                    conversation.current_line = 0;
+                }
+                Content::Choices(choices) => {
+                    // npcs choose randomly, without displaying anything
+                    match choices.choose(&mut rand::thread_rng()) {
+                        None => break,
+                        Some(choice) => {
+                            conversation.current_state = *choice.exit_state();
+                            conversation.current_line = 0;
+                        }
+                    }
+                }
+            }
+        }
+
+        let Some((source, line)) = next_line else {
+            end_conversation(&mut commands, group, conversation);
+            continue;
+        };
+
+        let Some(speaker) = conversation.roles.get(&source) else {
+            warn!("{source} is not in the conversation");
+            end_conversation(&mut commands, group, conversation);
+            continue;
+        };
+
+        let bubble = commands
+            .spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        line,
+                        TextStyle {
+                            font: conversation_resources.font.clone(),
+                            font_size: SPEECH_BUBBLE_FONT_SIZE,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_alignment(TextAlignment::Center),
+                    transform: Transform::from_translation(SPEECH_BUBBLE_TRANSLATION.into()),
+                    ..default()
+                },
+                SpeechBubble,
+                Name::new("Speech Bubble"),
+            ))
+            .id();
+        commands.entity(*speaker).add_child(bubble);
+        conversation.bubble = Some(bubble);
+        conversation.line_timer.reset();
+    }
+}
+
+/// The player joins the conversation by interacting with any member:
+/// all members turn to the player and wait for the end of the dialog.
+pub fn join_conversation(
+    mut commands: Commands,
+    mut character_interaction_events: EventReader<CharacterInteractionEvent>,
+
+    mut group_query: Query<&mut Conversation>,
+    member_query: Query<(&InConversation, &Transform)>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    for CharacterInteractionEvent(character) in character_interaction_events.iter() {
+        let Ok((InConversation(group), _)) = member_query.get(*character) else {
+            continue;
+        };
+        let (Ok(mut conversation), Ok(player_transform)) =
+            (group_query.get_mut(*group), player_query.get_single())
+        else {
+            continue;
+        };
+
+        if let Some(bubble) = conversation.bubble.take() {
+            commands.entity(bubble).despawn_recursive();
+        }
+        // The line will be said again after the dialog
+        conversation.current_line = conversation.current_line.saturating_sub(1);
+
+        for member in &conversation.members {
+            if let Ok((_, transform)) = member_query.get(*member) {
+                let direction = if player_transform.translation.x < transform.translation.x {
+                    Direction::Left
+                } else {
+                    Direction::Right
+                };
+                commands.entity(*member).insert(direction);
+            }
+        }
+    }
+}
+
+/// Members of a conversation which vanished (e.g. its landmarks were reloaded)
+/// go back to their occupations.
+pub fn leave_abandoned_conversations(
+    mut commands: Commands,
+    member_query: Query<(Entity, &InConversation)>,
+    group_query: Query<(), With<Conversation>>,
+) {
+    for (member, InConversation(group)) in &member_query {
+        if group_query.get(*group).is_err() {
+            commands
+                .entity(member)
+                .remove::<(InConversation, RestTime)>();
+        }
+    }
+}
+
+/// The members go back to their occupations.
+/// Occurs `OnExit(GameState::Playing)`
+///
+/// The groups are landmarks, kept between two games.
+pub fn clear_conversations(mut commands: Commands, group_query: Query<Entity, With<Conversation>>) {
+    for group in &group_query {
+        commands.entity(group).remove::<Conversation>();
+    }
+}
+
+fn end_conversation(commands: &mut Commands, group: Entity, conversation: &Conversation) {
+    commands.entity(group).remove::<Conversation>();
+    if let Some(bubble) = conversation.bubble {
+        if let Some(bubble) = commands.get_entity(bubble) {
+            bubble.despawn_recursive();
+        }
+    }
+    for member in &conversation.members {
+        if let Some(mut member) = commands.get_entity(*member) {
+            member.remove::<(InConversation, RestTime)>();
+        }
+    }
+}
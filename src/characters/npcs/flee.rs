@@ -0,0 +1,352 @@
+//! Flee Behavior
+//!
+//! The opposite of the Chase Behavior: the npc runs away from a threat,
+//! towards an exit or a free landmark of its room.
+//!
+//! - `Fleer`
+//! - The `PursuitRangeSensor` is reused:
+//!   if the threat exits this sensor, the npc stops fleeing.
+//! - The npc also gives up when the threat is gone or out of its pursuit radius
+//!   (e.g. alarmed from further), or after `FLEE_DURATION`.
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    characters::{
+        npcs::{
+            aggression::{DetectionRangeSensor, PursuitRangeSensor},
+            idle::RestTime,
+            movement::{Chaser, TargetSeeker},
+            profile::AIProfile,
+            NPC,
+        },
+        player::Player,
+        CharacterHitbox,
+    },
+    collisions::CollisionEventExt,
+    combat::{CombatEvent, FairPlayTimer, Hp, InCombat, Recruted, Reputation},
+    constants::{
+        character::npcs::movement::{FLEE_ALARM_RADIUS, FLEE_DURATION},
+        TILE_SIZE,
+    },
+    locations::{
+        landmarks::{Landmark, LandmarkStatus},
+        temple::{Location, LocationSensor},
+    },
+};
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Reflect, Component)]
+pub struct Fleer {
+    pub threat: Entity,
+    /// The exit or landmark the npc is running to.
+    /// If there is none, the npc just runs in the opposite direction of the threat.
+    pub refuge: Option<Entity>,
+    /// Gives up fleeing when finished.
+    pub timer: Timer,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Events                                   */
+/* -------------------------------------------------------------------------- */
+
+/// Happens when:
+///   - npc::flee::flee_from_combat
+///     - A combat started near a neutral npc
+///   - npc::flee::flee_when_badly_hurt
+///     - A combatant lost too much hp
+///
+/// Read in npc::flee::activate_flee_urge
+#[derive(Event)]
+pub struct FleeEvent {
+    pub npc_entity: Entity,
+    pub threat_entity: Entity,
+}
+
+/// Happens when:
+///   - npc::flee::flee_management
+///     - The threat exits the `PursuitRangeSensor` of the npc
+///   - npc::flee::give_up_flee
+///     - The threat is gone or far, or the npc fled long enough
+///
+/// Read in npc::flee::deactivate_flee_urge
+#[derive(Event)]
+pub struct StopFleeEvent {
+    pub npc_entity: Entity,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+/// - insert the `Fleer` with the best refuge available
+/// - give up any chase (with fair play)
+/// - turn on the pursuit sensor to know when the threat is left behind
+/// - turn off the detection sensor
+pub fn activate_flee_urge(
+    mut commands: Commands,
+    mut ev_flee: EventReader<FleeEvent>,
+
+    fleer_query: Query<(&Children, &Location, &Name, Option<&Chaser>)>,
+    profile_query: Query<&AIProfile>,
+    pos_query: Query<&GlobalTransform>,
+    landmark_sensor_query: Query<(Entity, &Landmark), With<Sensor>>,
+    exits_query: Query<(Entity, &LocationSensor)>,
+
+    pursuit_sensor_query: Query<Entity, (With<PursuitRangeSensor>, Without<DetectionRangeSensor>)>,
+    detection_sensor_query: Query<
+        Entity,
+        (With<DetectionRangeSensor>, Without<PursuitRangeSensor>),
+    >,
+) {
+    for FleeEvent {
+        npc_entity,
+        threat_entity,
+    } in ev_flee.iter()
+    {
+        let Ok((children, location, name, potential_chaser)) = fleer_query.get(*npc_entity) else {
+            continue;
+        };
+        let (Ok(npc_transform), Ok(threat_transform)) =
+            (pos_query.get(*npc_entity), pos_query.get(*threat_entity))
+        else {
+            continue;
+        };
+        info!("{} flees", name);
+
+        let npc_position = npc_transform.translation().truncate();
+        let threat_position = threat_transform.translation().truncate();
+
+        // Exits are prefered to landmarks: a refuge must lead away from the threat.
+        let free_landmarks = landmark_sensor_query
+            .iter()
+            .filter(|(_, landmark)| {
+                landmark.location == *location && landmark.status == LandmarkStatus::Free
+            })
+            .map(|(landmark, _)| landmark);
+        let exits = exits_query
+            .iter()
+            .filter(|(_, exit)| exit.location == *location)
+            .map(|(exit, _)| exit);
+
+        let mut refuge = None;
+        for (candidates, bonus) in [
+            (exits.collect::<Vec<Entity>>(), FLEE_ALARM_RADIUS),
+            (free_landmarks.collect::<Vec<Entity>>(), 0.),
+        ] {
+            for candidate in candidates {
+                if let Ok(candidate_transform) = pos_query.get(candidate) {
+                    let candidate_position = candidate_transform.translation().truncate();
+                    let score = candidate_position.distance(threat_position)
+                        - candidate_position.distance(npc_position);
+                    if score > 0. {
+                        match refuge {
+                            Some((_, best_score)) if best_score >= score + bonus => {}
+                            _ => refuge = Some((candidate, score + bonus)),
+                        }
+                    }
+                }
+            }
+        }
+
+        if potential_chaser.is_some() {
+            commands
+                .entity(*npc_entity)
+                .remove::<Chaser>()
+                .insert(FairPlayTimer::new(AIProfile::fair_play_time_of(
+                    profile_query.get(*npc_entity).ok(),
+                )));
+        }
+
+        commands
+            .entity(*npc_entity)
+            .remove::<RestTime>()
+            .insert(Fleer {
+                threat: *threat_entity,
+                refuge: refuge.map(|(refuge, _)| refuge),
+                timer: Timer::from_seconds(FLEE_DURATION, TimerMode::Once),
+            });
+
+        for collider in children {
+            if pursuit_sensor_query.get(*collider).is_ok() {
+                commands
+                    .entity(*collider)
+                    .insert(ActiveEvents::COLLISION_EVENTS);
+            } else if detection_sensor_query.get(*collider).is_ok() {
+                commands.entity(*collider).remove::<ActiveEvents>();
+            }
+        }
+    }
+}
+
+/// - remove the `Fleer`
+/// - turn off the pursuit sensor
+/// - turn on the detection sensor if the npc seeks targets
+pub fn deactivate_flee_urge(
+    mut commands: Commands,
+    mut ev_stop_flee: EventReader<StopFleeEvent>,
+
+    fleer_query: Query<(&Children, &Name, Option<&TargetSeeker>), With<Fleer>>,
+    pursuit_sensor_query: Query<Entity, (With<PursuitRangeSensor>, Without<DetectionRangeSensor>)>,
+    detection_sensor_query: Query<
+        Entity,
+        (With<DetectionRangeSensor>, Without<PursuitRangeSensor>),
+    >,
+) {
+    for StopFleeEvent { npc_entity } in ev_stop_flee.iter() {
+        let Ok((children, name, potential_target_seeker)) = fleer_query.get(*npc_entity) else {
+            continue;
+        };
+        info!("{} stops fleeing", name);
+
+        commands.entity(*npc_entity).remove::<Fleer>();
+
+        for collider in children {
+            if pursuit_sensor_query.get(*collider).is_ok() {
+                commands.entity(*collider).remove::<ActiveEvents>();
+            } else if detection_sensor_query.get(*collider).is_ok()
+                && potential_target_seeker.is_some()
+            {
+                commands
+                    .entity(*collider)
+                    .insert(ActiveEvents::COLLISION_EVENTS);
+            }
+        }
+    }
+}
+
+/// # Flee Management
+///
+/// - Threat Outran.
+///   If the threat exits the `PursuitRangeSensor` of the fleer,
+///   the fleer stops fleeing.
+pub fn flee_management(
+    mut collision_events: EventReader<CollisionEvent>,
+
+    character_hitbox_query: Query<&Parent, With<CharacterHitbox>>,
+    fleer_query: Query<&Fleer>,
+    pursuit_sensor_query: Query<&Parent, With<PursuitRangeSensor>>,
+
+    mut ev_stop_flee: EventWriter<StopFleeEvent>,
+) {
+    for collision_event in collision_events.iter() {
+        let (entity_1, entity_2) = collision_event.entities();
+
+        let (character, sensor) = match (
+            character_hitbox_query.get(entity_1),
+            character_hitbox_query.get(entity_2),
+        ) {
+            (Ok(character), Err(_)) => (**character, entity_2),
+            (Err(_), Ok(character)) => (**character, entity_1),
+            _ => continue,
+        };
+
+        if collision_event.is_stopped() {
+            if let Ok(npc) = pursuit_sensor_query.get(sensor) {
+                if let Ok(fleer) = fleer_query.get(**npc) {
+                    if fleer.threat == character {
+                        ev_stop_flee.send(StopFleeEvent { npc_entity: **npc });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// # Threat Gone
+///
+/// Stops the flee when the threat vanished or is out of the pursuit radius
+/// without ever leaving the `PursuitRangeSensor` (e.g. alarmed from further),
+/// or when the fleer fled long enough (e.g. hidden near a foe frozen in combat).
+pub fn give_up_flee(
+    time: Res<Time>,
+
+    mut fleer_query: Query<(Entity, &mut Fleer, &Transform, Option<&AIProfile>)>,
+    threat_query: Query<&Transform>,
+
+    mut ev_stop_flee: EventWriter<StopFleeEvent>,
+) {
+    for (npc, mut fleer, transform, potential_profile) in &mut fleer_query {
+        fleer.timer.tick(time.delta());
+
+        let threat_far = match threat_query.get(fleer.threat) {
+            Err(_) => true,
+            Ok(threat_transform) => {
+                threat_transform
+                    .translation
+                    .truncate()
+                    .distance(transform.translation.truncate())
+                    > AIProfile::pursuit_radius_of(potential_profile) * TILE_SIZE
+            }
+        };
+
+        if threat_far || fleer.timer.finished() {
+            ev_stop_flee.send(StopFleeEvent { npc_entity: npc });
+        }
+    }
+}
+
+/// Neutral npcs, in the same room, flee from a starting combat.
+pub fn flee_from_combat(
+    mut ev_combat: EventReader<CombatEvent>,
+
+    npc_query: Query<
+        (Entity, &Reputation, &Location, &Transform),
+        (
+            With<NPC>,
+            Without<Fleer>,
+            Without<InCombat>,
+            Without<Recruted>,
+        ),
+    >,
+    foe_query: Query<(&Location, &Transform)>,
+
+    mut ev_flee: EventWriter<FleeEvent>,
+) {
+    for CombatEvent { entity } in ev_combat.iter() {
+        let Ok((foe_location, foe_transform)) = foe_query.get(*entity) else {
+            continue;
+        };
+
+        for (npc, reputation, location, transform) in &npc_query {
+            if npc != *entity
+                && reputation.is_neutral()
+                && location == foe_location
+                && transform.translation.distance(foe_transform.translation) <= FLEE_ALARM_RADIUS
+            {
+                ev_flee.send(FleeEvent {
+                    npc_entity: npc,
+                    threat_entity: *entity,
+                });
+            }
+        }
+    }
+}
+
+/// A badly hurt combatant flees from the one they were fighting.
+pub fn flee_when_badly_hurt(
+    npc_query: Query<(Entity, &Hp, Option<&Chaser>), (Changed<Hp>, With<NPC>, Without<Fleer>)>,
+    player_query: Query<Entity, With<Player>>,
+
+    mut ev_flee: EventWriter<FleeEvent>,
+) {
+    for (npc, hp, potential_chaser) in &npc_query {
+        if hp.is_badly_hurt() {
+            let threat = match potential_chaser {
+                Some(chaser) => chaser.target,
+                None => match player_query.get_single() {
+                    Ok(player) => player,
+                    Err(_) => continue,
+                },
+            };
+            ev_flee.send(FleeEvent {
+                npc_entity: npc,
+                threat_entity: threat,
+            });
+        }
+    }
+}
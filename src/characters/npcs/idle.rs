@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::Velocity;
 
-use super::{movement::NPCBehavior, NPC};
+use super::{conversation::InConversation, movement::NPCBehavior, NPC};
 
 #[derive(Component)]
 pub struct RestTime {
@@ -13,15 +13,26 @@ pub fn flexing_timer(
     mut commands: Commands,
     time: Res<Time>,
     mut npc_query: Query<
-        (Entity, &mut RestTime, &mut Velocity, &Name),
+        (
+            Entity,
+            &mut RestTime,
+            &mut Velocity,
+            &Name,
+            Option<&InConversation>,
+        ),
         (With<NPC>, With<NPCBehavior>),
     >,
 ) {
-    for (npc, mut rest_timer, mut rb_vel, _name) in npc_query.iter_mut() {
-        rest_timer.timer.tick(time.delta());
-
+    for (npc, mut rest_timer, mut rb_vel, _name, potential_conversation) in npc_query.iter_mut() {
         rb_vel.linvel.x = 0.;
         rb_vel.linvel.y = 0.;
+
+        // The conversation decides when the rest is over
+        if potential_conversation.is_some() {
+            continue;
+        }
+
+        rest_timer.timer.tick(time.delta());
         // info!("{:#?}", rest_timer.timer);
         if rest_timer.timer.finished() {
             // info!(target: "Stop Rest", "{:?}, {}", npc, _name);
@@ -0,0 +1,222 @@
+//! Investigation of a lost target
+//!
+//! When a chase is canceled (the target outran the npc),
+//! or when an ally gave the alert, the npc
+//!
+//! - walks to the last known position of the target (gives up if it takes too long)
+//! - looks around for a while
+//! - goes back to the `NPCBehavior` it had before
+//!
+//! The previous behavior is kept aside: a reserved landmark taken
+//! in the meantime is replaced by a new free one.
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Sensor;
+
+use crate::{
+    characters::npcs::{
+        aggression::StopChaseEvent,
+        idle::RestTime,
+        movement::{Chaser, NPCBehavior},
+        NPC,
+    },
+    constants::character::npcs::investigation::*,
+    locations::{
+        landmarks::{seek_free_landmark, Direction, Landmark, LandmarkStatus},
+        temple::Location,
+    },
+};
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Reflect, Component)]
+pub struct Investigation {
+    pub last_known_position: Vec2,
+    /// Restored at the end of the investigation.
+    pub previous_behavior: NPCBehavior,
+    /// Runs until the npc reached the last known position.
+    walk_timer: Timer,
+    /// Starts when the npc reached the last known position.
+    look_around_timer: Timer,
+    turn_timer: Timer,
+}
+
+impl Investigation {
+    pub fn new(last_known_position: Vec2, previous_behavior: NPCBehavior) -> Self {
+        Investigation {
+            last_known_position,
+            previous_behavior,
+            walk_timer: Timer::from_seconds(INVESTIGATION_WALK_DURATION, TimerMode::Once),
+            look_around_timer: Timer::from_seconds(LOOK_AROUND_DURATION, TimerMode::Once),
+            turn_timer: Timer::from_seconds(LOOK_AROUND_TURN_DELAY, TimerMode::Repeating),
+        }
+    }
+
+    pub fn reached(&self, position: Vec2) -> bool {
+        position.distance(self.last_known_position) <= INVESTIGATION_DISTANCE
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+/// A npc losing its target investigates where it was last seen.
+///
+/// A caught target (`Chaser::close`) starts a combat instead.
+/// A target gone to another room is not followed: npcs can't path through rooms.
+pub fn investigate_lost_target(
+    mut commands: Commands,
+    mut ev_stop_chase: EventReader<StopChaseEvent>,
+
+    npc_query: Query<(&Chaser, &NPCBehavior, &Location, &Name), With<NPC>>,
+    target_query: Query<(&GlobalTransform, &Location)>,
+) {
+    for StopChaseEvent { npc_entity } in ev_stop_chase.iter() {
+        let Ok((chaser, behavior, location, name)) = npc_query.get(*npc_entity) else {
+            continue;
+        };
+        if chaser.close {
+            continue;
+        }
+        let Ok((target_transform, target_location)) = target_query.get(chaser.target) else {
+            continue;
+        };
+        if target_location != location {
+            continue;
+        }
+        info!("{} investigates", name);
+
+        commands
+            .entity(*npc_entity)
+            .remove::<RestTime>()
+            .insert(Investigation::new(
+                target_transform.translation().truncate(),
+                *behavior,
+            ));
+    }
+}
+
+/// Once at the last known position, the npc turns around a few times,
+/// then goes back to its previous behavior.
+/// It goes back too if the position can't be reached in time.
+pub fn look_around(
+    mut commands: Commands,
+    time: Res<Time>,
+
+    mut npc_query: Query<
+        (
+            Entity,
+            &mut Investigation,
+            &mut NPCBehavior,
+            &Transform,
+            Option<&Direction>,
+            &Name,
+        ),
+        (With<NPC>, Without<Chaser>),
+    >,
+    mut landmark_sensor_query: Query<(Entity, &mut Landmark), With<Sensor>>,
+    target_query: Query<(), With<Transform>>,
+) {
+    for (npc, mut investigation, mut behavior, transform, potential_direction, name) in
+        &mut npc_query
+    {
+        let reached = investigation.reached(transform.translation.truncate());
+        if reached {
+            investigation.look_around_timer.tick(time.delta());
+            investigation.turn_timer.tick(time.delta());
+        } else {
+            investigation.walk_timer.tick(time.delta());
+        }
+
+        if investigation.look_around_timer.finished() || investigation.walk_timer.finished() {
+            info!("{} ends the investigation", name);
+            *behavior = restore_behavior(
+                *behavior,
+                investigation.previous_behavior,
+                npc,
+                &mut landmark_sensor_query,
+                &target_query,
+            );
+            commands.entity(npc).remove::<(Investigation, Direction)>();
+        } else if reached && investigation.turn_timer.just_finished() {
+            let direction = match potential_direction {
+                Some(Direction::Left) => Direction::Right,
+                _ => Direction::Left,
+            };
+            commands.entity(npc).insert(direction);
+        }
+    }
+}
+
+/// A new chase overrides the investigation.
+pub fn abandon_investigation(
+    mut commands: Commands,
+    npc_query: Query<Entity, (Added<Chaser>, With<Investigation>)>,
+) {
+    for npc in &npc_query {
+        commands.entity(npc).remove::<(Investigation, Direction)>();
+    }
+}
+
+/// The previous behavior, still valid after the investigation.
+///
+/// - the landmark reserved by the current behavior (if any other) is freed
+/// - a landmark taken by someone else (or despawned) is replaced
+/// - a vanished followed target makes the npc camp
+fn restore_behavior(
+    current_behavior: NPCBehavior,
+    previous_behavior: NPCBehavior,
+    npc: Entity,
+    landmark_sensor_query: &mut Query<(Entity, &mut Landmark), With<Sensor>>,
+    target_query: &Query<(), With<Transform>>,
+) -> NPCBehavior {
+    if let NPCBehavior::LandmarkSeeking(current_destination, _) = current_behavior {
+        let kept = matches!(
+            previous_behavior,
+            NPCBehavior::LandmarkSeeking(destination, _) if destination == current_destination
+        );
+        if !kept {
+            if let Ok((_, mut landmark)) = landmark_sensor_query.get_mut(current_destination) {
+                if landmark.status == LandmarkStatus::ReservedBy(npc) {
+                    landmark.status = LandmarkStatus::Free;
+                }
+            }
+        }
+    }
+
+    match previous_behavior {
+        NPCBehavior::LandmarkSeeking(destination, location) => {
+            match landmark_sensor_query.get_mut(destination) {
+                Ok((_, mut landmark)) => {
+                    let still_available = match landmark.status {
+                        LandmarkStatus::Free => true,
+                        LandmarkStatus::ReservedBy(owner) | LandmarkStatus::OccupiedBy(owner) => {
+                            owner == npc
+                        }
+                    };
+                    if still_available {
+                        if landmark.status == LandmarkStatus::Free {
+                            landmark.status = LandmarkStatus::ReservedBy(npc);
+                        }
+                        landmark.reservation_timer.reset();
+                        previous_behavior
+                    } else {
+                        seek_free_landmark(landmark_sensor_query, location, npc)
+                    }
+                }
+                Err(_) => seek_free_landmark(landmark_sensor_query, location, npc),
+            }
+        }
+        NPCBehavior::Follow { target, .. } => {
+            if target_query.get(target).is_ok() {
+                NPCBehavior::follow(target, false)
+            } else {
+                NPCBehavior::Camping
+            }
+        }
+        NPCBehavior::Camping => NPCBehavior::Camping,
+    }
+}
@@ -0,0 +1,249 @@
+//! Memory of the npcs
+//!
+//! A npc remembers the notable events it took part in, or witnessed:
+//! being talked to, attacked, recruited then dismissed,
+//! or seeing the player open the secret banner.
+//!
+//! Each memory is timestamped and fades away after a while (see `Memory::strength`).
+//! The dialog conditions can check them (`RemembersAttack`, ...),
+//! as can any behavior through `Memory::remembers`.
+
+use bevy::prelude::*;
+use std::fmt;
+
+use crate::{
+    characters::{
+        npcs::{
+            movement::{FollowEvent, NPCBehavior},
+            CharacterInteractionEvent, NPC,
+        },
+        player::Player,
+    },
+    combat::CombatEvent,
+    constants::character::npcs::memory::*,
+    locations::temple::{main_room::SecretBannerEvent, Location},
+};
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Reflect, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MemoryKind {
+    TalkedTo,
+    Attacked,
+    SawSecretBanner,
+    Recruited,
+    Dismissed,
+}
+
+impl MemoryKind {
+    /// Seconds before being forgotten.
+    pub fn duration(&self) -> f32 {
+        match self {
+            MemoryKind::TalkedTo => TALKED_TO_MEMORY_DURATION,
+            MemoryKind::Attacked => ATTACKED_MEMORY_DURATION,
+            MemoryKind::SawSecretBanner => SAW_SECRET_BANNER_MEMORY_DURATION,
+            MemoryKind::Recruited => RECRUITED_MEMORY_DURATION,
+            MemoryKind::Dismissed => DISMISSED_MEMORY_DURATION,
+        }
+    }
+}
+
+/// Used as event in the dialog conditions.
+impl fmt::Display for MemoryKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MemoryKind::TalkedTo => write!(f, "RemembersTalk"),
+            MemoryKind::Attacked => write!(f, "RemembersAttack"),
+            MemoryKind::SawSecretBanner => write!(f, "RemembersSecretBanner"),
+            MemoryKind::Recruited => write!(f, "RemembersRecruitment"),
+            MemoryKind::Dismissed => write!(f, "RemembersDismissal"),
+        }
+    }
+}
+
+#[derive(Reflect, Clone, Copy, Debug)]
+pub struct Recollection {
+    pub kind: MemoryKind,
+    /// The other character involved (most of the time the player).
+    pub about: Entity,
+    /// In seconds since startup (`Time::elapsed_seconds`).
+    pub timestamp: f32,
+}
+
+#[derive(Reflect, Default, Debug, Component)]
+pub struct Memory {
+    recollections: Vec<Recollection>,
+}
+
+impl Memory {
+    /// A recollection of the same event is refreshed instead of duplicated.
+    pub fn remember(&mut self, kind: MemoryKind, about: Entity, now: f32) {
+        match self
+            .recollections
+            .iter_mut()
+            .find(|recollection| recollection.kind == kind && recollection.about == about)
+        {
+            Some(recollection) => recollection.timestamp = now,
+            None => self.recollections.push(Recollection {
+                kind,
+                about,
+                timestamp: now,
+            }),
+        }
+    }
+
+    pub fn remembers(&self, kind: MemoryKind) -> bool {
+        self.last(kind).is_some()
+    }
+
+    pub fn remembers_about(&self, kind: MemoryKind, about: Entity) -> bool {
+        self.recollections
+            .iter()
+            .any(|recollection| recollection.kind == kind && recollection.about == about)
+    }
+
+    /// The most recent recollection of this kind.
+    pub fn last(&self, kind: MemoryKind) -> Option<&Recollection> {
+        self.recollections
+            .iter()
+            .filter(|recollection| recollection.kind == kind)
+            .max_by(|a, b| a.timestamp.total_cmp(&b.timestamp))
+    }
+
+    /// From 1. (just happened) to 0. (forgotten)
+    pub fn strength(recollection: &Recollection, now: f32) -> f32 {
+        (1. - (now - recollection.timestamp) / recollection.kind.duration()).max(0.)
+    }
+
+    /// The kinds remembered, as dialog condition events.
+    pub fn dialog_events(&self) -> Vec<String> {
+        let mut events: Vec<String> = Vec::new();
+        for recollection in &self.recollections {
+            let event = recollection.kind.to_string();
+            if !events.contains(&event) {
+                events.push(event);
+            }
+        }
+        events
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+pub fn forget_old_memories(time: Res<Time>, mut memory_query: Query<&mut Memory>) {
+    let now = time.elapsed_seconds();
+    for mut memory in &mut memory_query {
+        if memory
+            .recollections
+            .iter()
+            .any(|recollection| Memory::strength(recollection, now) <= 0.)
+        {
+            memory
+                .recollections
+                .retain(|recollection| Memory::strength(recollection, now) > 0.);
+        }
+    }
+}
+
+pub fn remember_talks(
+    time: Res<Time>,
+    mut character_interaction_events: EventReader<CharacterInteractionEvent>,
+    player_query: Query<Entity, With<Player>>,
+    mut memory_query: Query<&mut Memory, With<NPC>>,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    for CharacterInteractionEvent(npc) in character_interaction_events.iter() {
+        if let Ok(mut memory) = memory_query.get_mut(*npc) {
+            memory.remember(MemoryKind::TalkedTo, player, time.elapsed_seconds());
+        }
+    }
+}
+
+/// The foe caught in a combat remembers being attacked by the player.
+pub fn remember_attacks(
+    time: Res<Time>,
+    mut ev_combat: EventReader<CombatEvent>,
+    player_query: Query<Entity, With<Player>>,
+    mut memory_query: Query<&mut Memory, With<NPC>>,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    for CombatEvent { entity } in ev_combat.iter() {
+        if let Ok(mut memory) = memory_query.get_mut(*entity) {
+            memory.remember(MemoryKind::Attacked, player, time.elapsed_seconds());
+        }
+    }
+}
+
+/// The npcs around the player, in the same room, see the secret banner being opened.
+pub fn witness_secret_banner(
+    time: Res<Time>,
+    mut secret_banner_event: EventReader<SecretBannerEvent>,
+    player_query: Query<(Entity, &Transform, &Location), With<Player>>,
+    mut npc_query: Query<(&mut Memory, &Transform, &Location, &Name), With<NPC>>,
+) {
+    let Ok((player, player_transform, player_location)) = player_query.get_single() else {
+        return;
+    };
+    for _ in secret_banner_event.iter() {
+        for (mut memory, transform, location, name) in &mut npc_query {
+            if location == player_location
+                && transform.translation.distance(player_transform.translation)
+                    <= SECRET_BANNER_WITNESS_RADIUS
+            {
+                info!("{} saw the secret banner", name);
+                memory.remember(MemoryKind::SawSecretBanner, player, time.elapsed_seconds());
+            }
+        }
+    }
+}
+
+/// A npc asked to follow the player is recruited;
+/// it is dismissed once it stops following.
+pub fn remember_recruitment(
+    time: Res<Time>,
+    mut follow_event: EventReader<FollowEvent>,
+    player_query: Query<Entity, With<Player>>,
+    mut memory_query: Query<&mut Memory, With<NPC>>,
+    changed_behavior_query: Query<(Entity, &NPCBehavior), (Changed<NPCBehavior>, With<Memory>)>,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    let now = time.elapsed_seconds();
+
+    for FollowEvent { npc, target } in follow_event.iter() {
+        if *target == player {
+            if let Ok(mut memory) = memory_query.get_mut(*npc) {
+                memory.remember(MemoryKind::Recruited, player, now);
+            }
+        }
+    }
+
+    for (npc, behavior) in &changed_behavior_query {
+        if matches!(behavior, NPCBehavior::Follow { target, .. } if *target == player) {
+            continue;
+        }
+        let Ok(mut memory) = memory_query.get_mut(npc) else {
+            continue;
+        };
+        let still_recruited = match (
+            memory.last(MemoryKind::Recruited),
+            memory.last(MemoryKind::Dismissed),
+        ) {
+            (Some(recruited), Some(dismissed)) => recruited.timestamp > dismissed.timestamp,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if still_recruited {
+            memory.remember(MemoryKind::Dismissed, player, now);
+        }
+    }
+}
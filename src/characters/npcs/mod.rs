@@ -1,39 +1,44 @@
 //! NPCs lockup
 
 pub mod aggression;
+pub mod alert;
+pub mod cat;
+pub mod conversation;
+pub mod flee;
 pub mod idle;
+pub mod investigation;
+pub mod memory;
 pub mod movement;
+pub mod perception;
+pub mod profile;
+pub mod roster;
+pub mod suspicion;
 
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
-use std::collections::{BTreeMap, HashMap};
-use yml_dialog::DialogNode;
+use std::collections::HashMap;
 
 use crate::{
     animations::{
         sprite_sheet_animation::{AnimationIndices, CharacterState},
-        CharacterSpriteSheet, GlobalAnimationIndices,
+        CharacterSpriteSheet,
     },
-    characters::{movement::MovementBundle, npcs::movement::NPCBehavior, CharacterHitbox},
-    combat::Reputation,
+    characters::movement::MovementBundle,
     constants::{
-        character::{npcs::*, player::PLAYER_SPAWN, *},
+        character::{
+            npcs::{cat::*, *},
+            *,
+        },
         interactions::INTERACT_BUTTON_SCALE,
     },
-    hud_opened,
+    hud_closed, hud_opened,
     interactions::{InteractIcon, Interactible, InteractionResources, InteractionSensor},
-    locations::{
-        landmarks::{reserved_random_free_landmark, Landmark},
-        temple::{Location, OverlappingEntity},
-    },
-    ui::dialog_systems::{CurrentInterlocutor, DialogMap},
-    GameState, HUDState,
+    locations::temple::{Location, OverlappingEntity},
+    ui::dialog_systems::CurrentInterlocutor,
+    GameState, HUDState, StateScoped,
 };
 
-use self::{
-    aggression::{DetectionRangeSensor, PursuitRangeSensor},
-    movement::{FollowRangeSensor, TargetSeeker, TargetType},
-};
+use self::cat::{CatBehavior, CatState};
 
 use super::player::Player;
 
@@ -45,6 +50,7 @@ pub struct NPCPlugin;
  *  - landwark
  *    - index in const, with free: bol
  *    - when talking to a npc in a landwark, include the other present
+ *      -> `conversation`
  *    -> rest
  *  - stroll
  *    - in a restricted zone -index in const-
@@ -62,6 +68,7 @@ pub struct NPCPlugin;
  *
  * Reflexion
  *  - should npc avoid hit other entity
+ *    -> `movement::avoid_crowd` steers them around each other and the MC
  *  - turn false the free param from a landmark position taken by the MC
  */
 impl Plugin for NPCPlugin {
@@ -74,9 +81,22 @@ impl Plugin for NPCPlugin {
             .add_event::<movement::FollowEvent>()
             .add_event::<aggression::StopChaseEvent>()
             .add_event::<aggression::EngagePursuitEvent>()
+            .add_event::<alert::AlertEvent>()
+            .add_event::<cat::PetCatEvent>()
+            .add_event::<flee::FleeEvent>()
+            .add_event::<flee::StopFleeEvent>()
+            .add_event::<perception::TargetPerceivedEvent>()
+            .add_systems(
+                Startup,
+                (conversation::load_conversations, profile::load_ai_profiles),
+            )
             .add_systems(
                 OnEnter(GameState::Playing),
-                (spawn_characters, spawn_vilains, spawn_cat),
+                (roster::spawn_roster, spawn_cat),
+            )
+            .add_systems(
+                OnExit(GameState::Playing),
+                conversation::clear_conversations,
             )
             .add_systems(
                 Update,
@@ -85,19 +105,80 @@ impl Plugin for NPCPlugin {
                     movement::follow_event,
                     movement::npc_behavior_change,
                     movement::chase_management.in_set(NPCSystems::Collision),
+                    movement::catch_after_invulnerability.in_set(NPCSystems::Collision),
+                    (
+                        perception::perceive_targets,
+                        (
+                            perception::engage_perceived_target,
+                            suspicion::suspicion_update,
+                        ),
+                    )
+                        .chain()
+                        .before(aggression::activate_pursuit_urge),
+                    (
+                        suspicion::spawn_suspicion_meter,
+                        suspicion::update_suspicion_meter,
+                    )
+                        .chain(),
                     aggression::activate_pursuit_urge.after(NPCSystems::Collision),
+                    (
+                        alert::propagate_alert,
+                        alert::answer_alert,
+                        alert::alert_decay,
+                    )
+                        .chain()
+                        .after(aggression::activate_pursuit_urge),
                     aggression::deactivate_pursuit_urge.after(NPCSystems::Collision),
+                    (
+                        investigation::investigate_lost_target
+                            .after(NPCSystems::Collision)
+                            .before(aggression::deactivate_pursuit_urge),
+                        investigation::look_around,
+                        investigation::abandon_investigation,
+                    ),
+                    (
+                        flee::flee_management.in_set(NPCSystems::Collision),
+                        flee::flee_from_combat,
+                        flee::flee_when_badly_hurt,
+                    )
+                        .before(flee::activate_flee_urge),
+                    flee::activate_flee_urge.after(NPCSystems::Collision),
+                    flee::give_up_flee.before(flee::deactivate_flee_urge),
+                    flee::deactivate_flee_urge.after(NPCSystems::Collision),
                     idle::flexing_timer
                         .in_set(NPCSystems::Idle)
                         .after(NPCSystems::Movement),
                     freeze_player_in_dialog.run_if(hud_opened),
+                    (
+                        conversation::start_conversation.after(NPCSystems::Idle),
+                        conversation::conversation_progress
+                            .after(conversation::start_conversation)
+                            .run_if(hud_closed),
+                        conversation::join_conversation,
+                        conversation::leave_abandoned_conversations
+                            .before(conversation::start_conversation),
+                    ),
+                    (cat::pet_cat, cat::startle_cat, cat::cat_brain).chain(),
+                    (
+                        memory::remember_talks,
+                        memory::remember_attacks,
+                        memory::witness_secret_banner,
+                        memory::remember_recruitment.after(movement::follow_event),
+                        memory::forget_old_memories,
+                    ),
+                    (profile::share_ai_profiles, profile::apply_ai_profile).chain(),
                 )
                     .run_if(in_state(GameState::Playing)),
             )
             .add_systems(
                 FixedUpdate,
-                movement::npc_movement
-                    .in_set(NPCSystems::Movement)
+                (
+                    movement::npc_movement.in_set(NPCSystems::Movement),
+                    movement::avoid_crowd
+                        .in_set(NPCSystems::Steering)
+                        .after(NPCSystems::Movement),
+                    cat::cat_movement.in_set(NPCSystems::Movement),
+                )
                     .run_if(in_state(GameState::Playing)),
             )
             .add_systems(
@@ -123,6 +204,8 @@ pub struct CharacterInteractionEvent(pub Entity);
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum NPCSystems {
     Movement,
+    /// Adjusts the velocity given by the `Movement`.
+    Steering,
     Collision,
     // --- OLD ---
     Stroll,
@@ -160,352 +243,14 @@ fn freeze_player_in_dialog(
     *character_state = CharacterState::Idle;
 }
 
-/// Cats and all friendly npc
-fn spawn_characters(
+fn spawn_cat(
     mut commands: Commands,
     characters_spritesheet: Res<CharacterSpriteSheet>,
-    mut dialogs: ResMut<DialogMap>,
-    global_animations_indices: Res<GlobalAnimationIndices>,
     interaction_resources: Res<InteractionResources>,
-    mut landmark_sensor_query: Query<(Entity, &mut Landmark), With<Sensor>>,
 ) {
-    /* -------------------------------------------------------------------------- */
-    /*                                    NPCs                                    */
-    /* -------------------------------------------------------------------------- */
-
-    let fabien_dialog_path = "data/fabien_dialog.yml";
-    let supreme_god_dialog_path = "data/supreme_god_dialog.yml";
-    let hugo_dialog_path = "data/hugo_dialog.yml";
-    // let olf_dialog_path = "data/olf_dialog.yml";
-
-    let mut npcs_infos = vec![
-        (
-            "Supreme God".to_string(),
-            SUPREME_GOD_LINE,
-            SUPREME_GOD_SPAWN_POSITION,
-            Reputation::new(100, 0),
-            NPCBehavior::Camping,
-            Location::Temple,
-            supreme_god_dialog_path,
-        ),
-        (
-            "Hugo".to_string(),
-            HEALER_V2_LINE,
-            PLAYER_SPAWN,
-            Reputation::new(100, 0),
-            NPCBehavior::Camping,
-            Location::SecretRoom,
-            hugo_dialog_path,
-        ),
-        (
-            "Vampire".to_string(),
-            VAMPIRE_LINE,
-            VAMPIRE_SPAWN_POSITION,
-            Reputation::new(100, 0),
-            NPCBehavior::LandmarkSeeking(
-                // match if there is none
-                reserved_random_free_landmark(&mut landmark_sensor_query, Location::Temple)
-                    .unwrap(),
-                Location::Temple,
-            ),
-            Location::Temple,
-            fabien_dialog_path,
-        ),
-    ];
-    for i in 0..5 {
-        npcs_infos.push((
-            format!("Fabien {}", i),
-            FABIEN_LOYAL_LINE,
-            FABIEN_SPAWN_POSITION,
-            Reputation::new(0, 0),
-            NPCBehavior::LandmarkSeeking(
-                // match if there is none
-                reserved_random_free_landmark(&mut landmark_sensor_query, Location::Temple)
-                    .unwrap(),
-                Location::Temple,
-            ),
-            Location::Temple,
-            fabien_dialog_path,
-        ));
-    }
-
-    for (name, spritesheet_line, spawn_position, reputation, behavior, location, dialog_path) in
-        npcs_infos
-    {
-        let mut npc_animation_indices = AnimationIndices(HashMap::new());
-        npc_animation_indices.insert(
-            CharacterState::Run,
-            global_animations_indices[spritesheet_line][0],
-        );
-        npc_animation_indices.insert(
-            CharacterState::Idle,
-            global_animations_indices[spritesheet_line][1],
-        );
-
-        let interactible = Interactible::new_npc();
-
-        let npc = commands
-            .spawn((
-                SpriteSheetBundle {
-                    texture_atlas: characters_spritesheet.texture_atlas.clone(),
-                    transform: Transform {
-                        translation: spawn_position.into(),
-                        scale: Vec3::splat(NPC_SCALE),
-                        ..default()
-                    },
-                    ..default()
-                },
-                Name::new(format!("NPC {}", name)),
-                NPC,
-                // -- Movement --
-                behavior,
-                MovementBundle {
-                    animation_indices: npc_animation_indices,
-                    ..default()
-                },
-                location,
-                // -- Social --
-                interactible,
-                reputation,
-                // -- Hitbox --
-                RigidBody::Dynamic,
-                LockedAxes::ROTATION_LOCKED,
-            ))
-            .with_children(|parent| {
-                parent.spawn((
-                    Collider::ball(15.),
-                    Transform::IDENTITY,
-                    Sensor,
-                    InteractionSensor,
-                    Name::new(format!("{} Interaction Sensor", name)),
-                ));
-
-                parent.spawn((
-                    SpriteBundle {
-                        texture: interaction_resources.interact_button.clone(),
-                        transform: Transform {
-                            translation: interactible.icon_translation,
-                            scale: Vec3::splat(INTERACT_BUTTON_SCALE),
-                            ..default()
-                        },
-                        visibility: Visibility::Hidden,
-                        ..default()
-                    },
-                    InteractIcon,
-                ));
-
-                parent.spawn((
-                    Collider::cuboid(CHAR_HITBOX_WIDTH, CHAR_HITBOX_HEIGHT),
-                    Transform::from_xyz(0., CHAR_HITBOX_Y_OFFSET, 0.),
-                    CharacterHitbox,
-                    Name::new(format!("{} Hitbox", name)),
-                ));
-
-                // REFACTOR: Spawn all sensor (but with a component "InactiveSensor" or query With<ActiveEvents>)
-
-                // parent.spawn((
-                //     Collider::ball(10.),
-                //     Sensor,
-                //     ActiveEvents::COLLISION_EVENTS,
-                //     ActiveCollisionTypes::STATIC_STATIC,
-                //     CharacterCloseSensor,
-                //     Name::new(format!("{} Close Sensor", name)),
-                // ));
-
-                // parent.spawn((
-                //     Collider::ball(60.),
-                //     // ActiveEvents::COLLISION_EVENTS,
-                //     Sensor,
-                //     PursuitRangeSensor,
-                //     Name::new(format!("{} Pursuit Range", name)),
-                // ));
-
-                // parent.spawn((
-                //     Collider::ball(40.),
-                //     // ActiveEvents::COLLISION_EVENTS,
-                //     Sensor,
-                //     DetectionRangeSensor,
-                //     Name::new(format!("{} Detection Range", name)),
-                // ));
-
-                parent.spawn((
-                    Collider::ball(20.),
-                    // ActiveEvents::COLLISION_EVENTS,
-                    Sensor,
-                    FollowRangeSensor,
-                    Name::new(format!("{} Follow Range", name)),
-                ));
-            })
-            .id();
-
-        let dialog_file = std::fs::File::open(dialog_path).unwrap();
-        let npc_deserialized_map: BTreeMap<usize, DialogNode> =
-            serde_yaml::from_reader(dialog_file).unwrap();
-        dialogs.insert(
-            npc,
-            (
-                *npc_deserialized_map.first_key_value().unwrap().0,
-                npc_deserialized_map,
-            ),
-        );
-    }
-}
-
-/// All vilain npc
-///
-/// Merge all spawn npcs function
-fn spawn_vilains(
-    mut commands: Commands,
-    characters_spritesheet: Res<CharacterSpriteSheet>,
-    mut dialogs: ResMut<DialogMap>,
-    global_animations_indices: Res<GlobalAnimationIndices>,
-    interaction_resources: Res<InteractionResources>,
-    mut landmark_sensor_query: Query<(Entity, &mut Landmark), With<Sensor>>,
-) {
-    /* -------------------------------------------------------------------------- */
-    /*                                   Vilains                                  */
-    /* -------------------------------------------------------------------------- */
-
-    let olf_dialog_path = "data/olf_dialog.yml";
-
-    let npcs_infos = vec![(
-        "Olf",
-        OLF_LINE,
-        OLF_SPAWN_POSITION,
-        Reputation::new(0, 100),
-        NPCBehavior::LandmarkSeeking(
-            // match if there is none
-            reserved_random_free_landmark(&mut landmark_sensor_query, Location::SecretRoom)
-                .unwrap(),
-            Location::SecretRoom,
-        ),
-        olf_dialog_path,
-    )];
-
-    for (name, spritesheet_line, spawn_position, reputation, behavior, dialog_path) in npcs_infos {
-        let mut npc_animation_indices = AnimationIndices(HashMap::new());
-        npc_animation_indices.insert(
-            CharacterState::Run,
-            global_animations_indices[spritesheet_line][0],
-        );
-        npc_animation_indices.insert(
-            CharacterState::Idle,
-            global_animations_indices[spritesheet_line][1],
-        );
-
-        let interactible = Interactible::new_npc();
-
-        let npc = commands
-            .spawn((
-                SpriteSheetBundle {
-                    texture_atlas: characters_spritesheet.texture_atlas.clone(),
-                    transform: Transform {
-                        translation: spawn_position.into(),
-                        scale: Vec3::splat(NPC_SCALE),
-                        ..default()
-                    },
-                    ..default()
-                },
-                Name::new(format!("NPC {}", name)),
-                NPC,
-                // -- Movement --
-                behavior,
-                MovementBundle {
-                    animation_indices: npc_animation_indices,
-                    ..default()
-                },
-                Location::SecretRoom,
-                // -- Social --
-                interactible,
-                reputation,
-                TargetSeeker(TargetType::Player),
-                // -- Hitbox --
-                RigidBody::Dynamic,
-                LockedAxes::ROTATION_LOCKED,
-            ))
-            .with_children(|parent| {
-                parent.spawn((
-                    Collider::ball(15.),
-                    Transform::IDENTITY,
-                    Sensor,
-                    InteractionSensor,
-                    Name::new(format!("{} Interaction Sensor", name)),
-                ));
-
-                parent.spawn((
-                    SpriteBundle {
-                        texture: interaction_resources.interact_button.clone(),
-                        transform: Transform {
-                            translation: interactible.icon_translation,
-                            scale: Vec3::splat(INTERACT_BUTTON_SCALE),
-                            ..default()
-                        },
-                        visibility: Visibility::Hidden,
-                        ..default()
-                    },
-                    InteractIcon,
-                ));
-
-                parent.spawn((
-                    Collider::cuboid(CHAR_HITBOX_WIDTH, CHAR_HITBOX_HEIGHT),
-                    Transform::from_xyz(0., CHAR_HITBOX_Y_OFFSET, 0.),
-                    CharacterHitbox,
-                    Name::new(format!("{} Hitbox", name)),
-                ));
-
-                // parent.spawn((
-                //     Collider::ball(10.),
-                //     Sensor,
-                //     ActiveEvents::COLLISION_EVENTS,
-                //     ActiveCollisionTypes::STATIC_STATIC,
-                //     CharacterCloseSensor,
-                //     Name::new(format!("{} Close Sensor", name)),
-                // ));
-
-                parent.spawn((
-                    Collider::ball(60.),
-                    // ActiveEvents::COLLISION_EVENTS,
-                    Sensor,
-                    PursuitRangeSensor,
-                    Name::new(format!("{} Pursuit Range", name)),
-                ));
-
-                parent.spawn((
-                    Collider::ball(40.),
-                    ActiveEvents::COLLISION_EVENTS,
-                    Sensor,
-                    DetectionRangeSensor,
-                    Name::new(format!("{} Detection Range", name)),
-                ));
-
-                // parent.spawn((
-                //     Collider::ball(20.),
-                //     // ActiveEvents::COLLISION_EVENTS,
-                //     Sensor,
-                //     FollowRangeSensor,
-                //     Name::new(format!("{} Follow Range", name)),
-                // ));
-            })
-            .id();
-
-        let dialog_file = std::fs::File::open(dialog_path).unwrap();
-        let npc_deserialized_map: BTreeMap<usize, DialogNode> =
-            serde_yaml::from_reader(dialog_file).unwrap();
-        dialogs.insert(
-            npc,
-            (
-                *npc_deserialized_map.first_key_value().unwrap().0,
-                npc_deserialized_map,
-            ),
-        );
-    }
-}
-
-fn spawn_cat(mut commands: Commands, characters_spritesheet: Res<CharacterSpriteSheet>) {
     /* -------------------------------------------------------------------------- */
     /*                                   Olf Cat                                  */
     /* -------------------------------------------------------------------------- */
-    // TODO: Polish #visual - Cat like movement
     let mut cat_animation_indices = AnimationIndices(HashMap::new());
     cat_animation_indices.insert(
         CharacterState::Run,
@@ -524,7 +269,9 @@ fn spawn_cat(mut commands: Commands, characters_spritesheet: Res<CharacterSprite
         ),
     );
 
-    // TEMP: Static Olf cat
+    let interactible =
+        Interactible::new(CAT_INTERACT_BUTTON_POSITION.into(), CAT_PET_INTERACTION_ID);
+
     commands
         .spawn((
             SpriteSheetBundle {
@@ -537,16 +284,45 @@ fn spawn_cat(mut commands: Commands, characters_spritesheet: Res<CharacterSprite
                 ..default()
             },
             Name::new("Olf Cat"),
+            StateScoped(GameState::Playing),
             OlfCat,
+            // -- Movement --
+            CatBehavior::new(CatState::Wander(Vec3::from(OLF_CAT_POSITION).truncate())),
             MovementBundle {
                 animation_indices: cat_animation_indices,
                 overlapping_entity: OverlappingEntity::new(CAT_SWITCH_Z_OFFSET),
                 ..default()
             },
+            Location::SecretRoom,
+            // -- Social --
+            interactible,
             // -- Hitbox --
-            RigidBody::Fixed,
+            RigidBody::Dynamic,
+            LockedAxes::ROTATION_LOCKED,
         ))
         .with_children(|parent| {
+            parent.spawn((
+                Collider::ball(10.),
+                Transform::IDENTITY,
+                Sensor,
+                InteractionSensor,
+                Name::new("Olf Cat Interaction Sensor"),
+            ));
+
+            parent.spawn((
+                SpriteBundle {
+                    texture: interaction_resources.interact_button.clone(),
+                    transform: Transform {
+                        translation: interactible.icon_translation,
+                        scale: Vec3::splat(INTERACT_BUTTON_SCALE),
+                        ..default()
+                    },
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                InteractIcon,
+            ));
+
             parent.spawn((
                 Collider::cuboid(2.5, 1.),
                 Transform::from_translation(OLF_CAT_HITBOX_OFFSET.into()),
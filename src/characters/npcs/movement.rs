@@ -12,20 +12,26 @@ use crate::{
     characters::{
         movement::{CharacterCloseSensor, Speed},
         npcs::{
-            aggression::{
-                DetectionRangeSensor, EngagePursuitEvent, PursuitRangeSensor, StopChaseEvent,
-            },
+            aggression::{DetectionRangeSensor, PursuitRangeSensor, StopChaseEvent},
+            flee::Fleer,
             idle::RestTime,
-            NPC,
+            investigation::Investigation,
+            OlfCat, NPC,
         },
-        player::Player,
+        player::{Invulnerable, Player},
         CharacterHitbox,
     },
     collisions::CollisionEventExt,
-    combat::{CombatEvent, FairPlayTimer, Reputation},
-    constants::character::CHAR_HITBOX_Y_OFFSET,
+    combat::CombatEvent,
+    constants::{
+        character::{
+            npcs::movement::{AVOIDANCE_RADIUS, SEPARATION_WEIGHT},
+            CHAR_HITBOX_Y_OFFSET,
+        },
+        locations::landmarks::LANDMARK_SENSOR_SIZE,
+    },
     locations::{
-        landmarks::{reserved_random_free_landmark, Direction, Landmark, LandmarkStatus},
+        landmarks::{seek_free_landmark, Direction, Landmark, LandmarkStatus},
         temple::Location,
     },
 };
@@ -184,7 +190,10 @@ pub fn animation(
             &mut TextureAtlasSprite,
             Option<&Direction>,
         ),
-        (Or<(Changed<Velocity>, Changed<Direction>)>, With<NPC>),
+        (
+            Or<(Changed<Velocity>, Changed<Direction>)>,
+            Or<(With<NPC>, With<OlfCat>)>,
+        ),
     >,
 ) {
     for (rb_vel, mut npc_state, mut texture_atlas_sprite, potential_forced_direction) in
@@ -231,6 +240,8 @@ pub fn npc_movement(
             Entity,
             &mut NPCBehavior,
             Option<&Chaser>,
+            Option<&Fleer>,
+            Option<&Investigation>,
             &Transform,
             &Speed,
             &mut Velocity,
@@ -244,24 +255,68 @@ pub fn npc_movement(
     location_query: Query<&Location>,
     mut ev_stop_chase: EventWriter<StopChaseEvent>,
 ) {
-    for (npc, mut behavior, potential_chaser, transform, speed, mut rb_vel, npc_name) in
-        &mut npc_query
+    for (
+        npc,
+        mut behavior,
+        potential_chaser,
+        potential_fleer,
+        potential_investigation,
+        transform,
+        speed,
+        mut rb_vel,
+        npc_name,
+    ) in &mut npc_query
     {
-        let (vel_x, vel_y) = match potential_chaser {
-            None => match *behavior {
+        if let Some(fleer) = potential_fleer {
+            let (vel_x, vel_y) = flee(fleer, transform, speed, &pos_query);
+            rb_vel.linvel.x = vel_x;
+            rb_vel.linvel.y = vel_y;
+            continue;
+        }
+
+        // The destination might have been despawned (e.g. landmark files reloaded)
+        if let NPCBehavior::LandmarkSeeking(destination, location) = *behavior {
+            if landmark_sensor_query.get(destination).is_err() {
+                *behavior = seek_free_landmark(&mut landmark_sensor_query, location, npc);
+            }
+        }
+
+        let (vel_x, vel_y) = match (potential_chaser, potential_investigation) {
+            // Check where the target was last seen, then look around (see `investigation`)
+            (None, Some(investigation)) => {
+                if investigation.reached(transform.translation.truncate()) {
+                    (0., 0.)
+                } else {
+                    let last_known_transform = GlobalTransform::from_translation(
+                        investigation.last_known_position.extend(0.),
+                    );
+                    move_to(&last_known_transform, false, transform, speed)
+                }
+            }
+            (None, None) => match *behavior {
                 NPCBehavior::Camping => (0., 0.),
                 NPCBehavior::LandmarkSeeking(destination, location) => {
-                    let (_, landmark) = landmark_sensor_query.get(destination).unwrap();
-                    let landmark_transform = pos_query.get(destination).unwrap();
+                    let (Ok((_, landmark)), Ok(landmark_transform)) = (
+                        landmark_sensor_query.get(destination),
+                        pos_query.get(destination),
+                    ) else {
+                        // e.g. the landmarks were reloaded
+                        *behavior = seek_free_landmark(&mut landmark_sensor_query, location, npc);
+                        continue;
+                    };
                     match landmark.status {
                         LandmarkStatus::OccupiedBy(_) => {
-                            // FIXME: Match the LandmarkReservationError
-                            let next_destination =
-                                reserved_random_free_landmark(&mut landmark_sensor_query, location)
-                                    .unwrap();
-                            *behavior = NPCBehavior::LandmarkSeeking(next_destination, location);
-                            let next_transform = pos_query.get(next_destination).unwrap();
-                            move_to(next_transform, false, transform, speed)
+                            *behavior =
+                                seek_free_landmark(&mut landmark_sensor_query, location, npc);
+                            match *behavior {
+                                NPCBehavior::LandmarkSeeking(next_destination, _) => {
+                                    let Ok(next_transform) = pos_query.get(next_destination) else {
+                                        continue;
+                                    };
+                                    move_to(next_transform, false, transform, speed)
+                                }
+                                _ => (0., 0.),
+                            }
                         }
                         _ => move_to(landmark_transform, false, transform, speed),
                     }
@@ -270,24 +325,31 @@ pub fn npc_movement(
                     if close {
                         (0., 0.)
                     } else {
-                        let target_transform = pos_query.get(target).unwrap();
+                        let Ok(target_transform) = pos_query.get(target) else {
+                            continue;
+                        };
                         move_to(target_transform, true, transform, speed)
                     }
                 }
             },
-            Some(Chaser { target, close }) => {
+            (Some(Chaser { target, close }), _) => {
                 if *close {
                     (0., 0.)
                 } else {
-                    let [npc_location, target_location] =
-                        location_query.get_many([*target, npc]).unwrap();
+                    let Ok([npc_location, target_location]) =
+                        location_query.get_many([*target, npc])
+                    else {
+                        continue;
+                    };
 
                     if target_location != npc_location {
                         ev_stop_chase.send(StopChaseEvent { npc_entity: npc });
                         info!("{} change zone. {:?}: chase canceled", npc_name, *target);
                         (0., 0.)
                     } else {
-                        let target_transform = pos_query.get(*target).unwrap();
+                        let Ok(target_transform) = pos_query.get(*target) else {
+                            continue;
+                        };
                         move_to(target_transform, true, transform, speed)
                     }
                 }
@@ -299,29 +361,91 @@ pub fn npc_movement(
     }
 }
 
+/// # Crowd Avoidance
+///
+/// Separation-based steering, layered on the velocity given by `npc_movement`.
+///
+/// Each moving npc is pushed away from the characters too close to them
+/// (npcs, cat or player).
+/// If the push is opposed to the npc's direction (head-on),
+/// the npc sidesteps instead of jittering in front of the other.
+///
+/// The chased or followed target is not avoided.
+pub fn avoid_crowd(
+    mut npc_query: Query<
+        (
+            Entity,
+            &NPCBehavior,
+            Option<&Chaser>,
+            &Transform,
+            &mut Velocity,
+        ),
+        With<NPC>,
+    >,
+    characters_query: Query<(Entity, &Transform), Or<(With<NPC>, With<OlfCat>, With<Player>)>>,
+) {
+    for (npc, behavior, potential_chaser, transform, mut rb_vel) in &mut npc_query {
+        if rb_vel.linvel == Vec2::ZERO {
+            continue;
+        }
+
+        let target = match (potential_chaser, behavior) {
+            (Some(Chaser { target, .. }), _) | (None, NPCBehavior::Follow { target, .. }) => {
+                Some(*target)
+            }
+            _ => None,
+        };
+
+        let position = transform.translation.truncate();
+        let mut separation = Vec2::ZERO;
+        for (character, character_transform) in &characters_query {
+            if character == npc || Some(character) == target {
+                continue;
+            }
+            let offset = position - character_transform.translation.truncate();
+            let distance = offset.length();
+            if distance > 0. && distance < AVOIDANCE_RADIUS {
+                // The closer the other is, the stronger the push
+                separation += offset / distance * (1. - distance / AVOIDANCE_RADIUS);
+            }
+        }
+
+        if separation == Vec2::ZERO {
+            continue;
+        }
+
+        let direction = rb_vel.linvel.normalize();
+        if direction.dot(separation.normalize()) < -0.9 {
+            // Head-on: always sidestep to the same side to not mirror the other
+            separation = separation.length() * direction.perp();
+        }
+
+        // Only the direction is steered: a slowed down npc (e.g. arriving) stays slow
+        let steered = direction + separation * SEPARATION_WEIGHT;
+        if let Some(steered_direction) = steered.try_normalize() {
+            rb_vel.linvel = steered_direction * rb_vel.linvel.length();
+        }
+    }
+}
+
 /// # Chase Management
 ///
 /// - Follow Close Update.
 ///   If a character enters/exits the npc's `FollowRangeSensor`,
 ///   it will update the `close` field in the npc's behavior.
 /// - Chase Failed.
-///   If a character exits the npc's `PursuitRangeSensor`,
+///   If a character (the player or a fleeing npc) exits the npc's `PursuitRangeSensor`,
 ///   and is the target of the npc, the npc will disengage with them.
 /// - Chase Completed.
 ///   If a npc enters the `CharacterCloseSensor` of their target,
 ///   it will trigger the `CombatEvent`.
-/// - Target Detection.
-///   If a character enters the `DetectionRangeSensor` of an enemy npc,
-///   seeking of targets, the npc will engage with the character.
+///
+/// The Target Detection is done by `perception::perceive_targets`.
 pub fn chase_management(
     mut collision_events: EventReader<CollisionEvent>,
     // rapier_context: Res<RapierContext>,
     character_hitbox_query: Query<(&Parent, &Name), With<CharacterHitbox>>,
-    player_query: Query<Entity, With<Player>>,
     mut npc_query: Query<(&mut NPCBehavior, Option<&mut Chaser>, &Name), With<NPC>>,
-    target_seeker_query: Query<&TargetSeeker>,
-    fair_play_timer_query: Query<Entity, With<FairPlayTimer>>,
-    reputation_query: Query<&Reputation>,
 
     follow_sensor_query: Query<
         (Entity, &Parent),
@@ -339,19 +463,9 @@ pub fn chase_management(
             Without<DetectionRangeSensor>,
         ),
     >,
-    detection_sensor_query: Query<
-        (Entity, &Parent),
-        (
-            With<DetectionRangeSensor>,
-            Without<PursuitRangeSensor>,
-            Without<FollowRangeSensor>,
-        ),
-    >,
     close_sensor_query: Query<(Entity, &Parent), With<CharacterCloseSensor>>,
+    invulnerable_query: Query<(), With<Invulnerable>>,
 
-    location_query: Query<&Location>,
-
-    mut ev_engage_pursuit: EventWriter<EngagePursuitEvent>,
     mut ev_combat: EventWriter<CombatEvent>,
     mut ev_stop_chase: EventWriter<StopChaseEvent>,
 ) {
@@ -371,6 +485,27 @@ pub fn chase_management(
         ) {
             (Err(_), Ok((character, character_name)))
             | (Ok((character, character_name)), Err(_)) => {
+                /* -------------------------------------------------------------------------- */
+                /*                                Chase Failed                                */
+                /* -------------------------------------------------------------------------- */
+                // Whether the target is the player or another npc (ie. a `Fleer`)
+                if collision_event.is_stopped() {
+                    if let (Ok((_pursuit_sensor, npc)), Err(_))
+                    | (Err(_), Ok((_pursuit_sensor, npc))) = (
+                        pursuit_sensor_query.get(entity_1),
+                        pursuit_sensor_query.get(entity_2),
+                    ) {
+                        if let Ok((_, Some(chaser), npc_name)) = npc_query.get(**npc) {
+                            if chaser.target == **character {
+                                // The npc has their target leaving their `PursuitRangeSensor`
+                                ev_stop_chase.send(StopChaseEvent { npc_entity: **npc });
+                                info!("{} outran {}: chase canceled", character_name, npc_name);
+                            }
+                        }
+                        continue;
+                    }
+                }
+
                 match npc_query.get_mut(**character) {
                     Err(_) => {
                         /* -------------------------------------------------------------------------- */
@@ -404,93 +539,7 @@ pub fn chase_management(
                             _ => {}
                         }
 
-                        /* -------------------------------------------------------------------------- */
-                        /*                               Or Chase Failed                              */
-                        /* -------------------------------------------------------------------------- */
-                        if collision_event.is_stopped() {
-                            match (
-                                pursuit_sensor_query.get(entity_1),
-                                pursuit_sensor_query.get(entity_2),
-                            ) {
-                                (Ok((_pursuit_sensor, npc)), Err(_))
-                                | (Err(_), Ok((_pursuit_sensor, npc))) => {
-                                    let (_, potential_chaser, npc_name) =
-                                        npc_query.get_mut(**npc).unwrap();
-                                    match potential_chaser {
-                                        None => {}
-                                        Some(chaser) => {
-                                            if chaser.target == **character {
-                                                // The npc has their target leaving their `PursuitRangeSensor`
-                                                ev_stop_chase
-                                                    .send(StopChaseEvent { npc_entity: **npc });
-                                                info!(
-                                                    "{} outran {}: chase canceled",
-                                                    character_name, npc_name
-                                                );
-                                            }
-                                        }
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
-                        /* -------------------------------------------------------------------------- */
-                        /*                           Or Detection Triggered                           */
-                        /* -------------------------------------------------------------------------- */
-                        else if collision_event.is_started() {
-                            match (
-                                detection_sensor_query.get(entity_1),
-                                detection_sensor_query.get(entity_2),
-                            ) {
-                                (Ok((_detection_sensor, npc)), Err(_))
-                                | (Err(_), Ok((_detection_sensor, npc))) => {
-                                    let (_, potential_chaser, npc_name) =
-                                        npc_query.get(**npc).unwrap();
-                                    if potential_chaser.is_none()
-                                        && target_seeker_query.get(**npc).is_ok()
-                                        && fair_play_timer_query.get(**npc).is_err()
-                                    {
-                                        let TargetSeeker(target_type) =
-                                            target_seeker_query.get(**npc).unwrap();
-                                        // The npc has a potential target entering their DetectionRangeSensor
-                                        let [npc_reputation, character_reputation] =
-                                            reputation_query
-                                                .get_many([**npc, **character])
-                                                .unwrap();
-                                        // DOC: new name ?
-                                        let is_target_type = match target_type {
-                                            TargetType::Player => {
-                                                player_query.get(**character).is_ok()
-                                            }
-                                            TargetType::Enemy => !npc_reputation
-                                                .in_the_same_team(character_reputation),
-                                            TargetType::Ally => npc_reputation
-                                                .in_the_same_team(character_reputation),
-                                            TargetType::Special(target) => *target == **character,
-                                        };
-
-                                        if is_target_type {
-                                            // The Target must be in the same zone
-                                            let [npc_location, target_location] = location_query
-                                                .get_many([**character, **npc])
-                                                .unwrap();
-
-                                            if npc_location == target_location {
-                                                ev_engage_pursuit.send(EngagePursuitEvent {
-                                                    npc_entity: **npc,
-                                                    target_entity: **character,
-                                                });
-                                                info!(
-                                                    "{} detected {}: chase initialized",
-                                                    npc_name, character_name
-                                                );
-                                            }
-                                        }
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
+                        // Detection is handled by `perception::perceive_targets`
                     }
                     Ok((_, potential_chaser, npc_name)) => {
                         /* -------------------------------------------------------------------------- */
@@ -506,8 +555,10 @@ pub fn chase_management(
                                 ) {
                                     (Ok((_close_sensor, closed_character)), Err(_))
                                     | (Err(_), Ok((_close_sensor, closed_character))) => {
+                                        // A dashing target slips through
                                         if chaser.target == **closed_character
                                             && collision_event.is_started()
+                                            && invulnerable_query.get(**closed_character).is_err()
                                         {
                                             // The npc entered the close sensor of their target
                                             chaser.close = true;
@@ -539,6 +590,54 @@ pub fn chase_management(
     }
 }
 
+/// # Chase Completed, after a dash
+///
+/// A chaser already in the `CharacterCloseSensor` of their target
+/// when the target's i-frames wear off won't trigger any new `CollisionEvent`:
+/// the intersection is checked again instead.
+pub fn catch_after_invulnerability(
+    rapier_context: Res<RapierContext>,
+    mut no_longer_invulnerable: RemovedComponents<Invulnerable>,
+
+    mut chaser_query: Query<(Entity, &mut Chaser, &Children, &Name), With<NPC>>,
+    target_query: Query<&Children>,
+    character_hitbox_query: Query<(), With<CharacterHitbox>>,
+    close_sensor_query: Query<(), With<CharacterCloseSensor>>,
+
+    mut ev_combat: EventWriter<CombatEvent>,
+    mut ev_stop_chase: EventWriter<StopChaseEvent>,
+) {
+    for target in no_longer_invulnerable.iter() {
+        let Ok(target_colliders) = target_query.get(target) else {
+            continue;
+        };
+        let Some(close_sensor) = target_colliders
+            .iter()
+            .find(|collider| close_sensor_query.get(**collider).is_ok())
+        else {
+            continue;
+        };
+
+        for (npc, mut chaser, colliders, npc_name) in &mut chaser_query {
+            if chaser.target != target {
+                continue;
+            }
+            let inside = colliders.iter().any(|collider| {
+                character_hitbox_query.get(*collider).is_ok()
+                    && rapier_context.intersection_pair(*collider, *close_sensor) == Some(true)
+            });
+            if inside {
+                chaser.close = true;
+                ev_combat.send(CombatEvent { entity: npc });
+                ev_stop_chase.send(StopChaseEvent { npc_entity: npc });
+                info!("Target Caught in 4K by {:?} {}", npc, npc_name);
+                // One combat at a time
+                break;
+            }
+        }
+    }
+}
+
 /// Give velocity x and y value to move forward a certain target
 fn move_to(
     target_transform: &GlobalTransform,
@@ -575,3 +674,45 @@ fn move_to(
 
     (vel_x, vel_y)
 }
+
+/// Give velocity x and y value to run away from a certain threat
+fn move_away(
+    threat_transform: &GlobalTransform,
+    transform: &Transform,
+    speed: &Speed,
+) -> (f32, f32) {
+    let (vel_x, vel_y) = move_to(threat_transform, true, transform, speed);
+    (-vel_x, -vel_y)
+}
+
+/// Run to the refuge if there is one, or just away from the threat.
+/// Stay still once hidden in the refuge.
+fn flee(
+    Fleer { threat, refuge, .. }: &Fleer,
+    transform: &Transform,
+    speed: &Speed,
+    pos_query: &Query<&GlobalTransform>,
+) -> (f32, f32) {
+    match refuge.and_then(|refuge| pos_query.get(refuge).ok()) {
+        // A refuge despawned (hot reload) leaves the fleer running away from the threat
+        Some(refuge_transform) => {
+            let hitbox_position =
+                transform.translation.truncate() + Vec2::new(0., CHAR_HITBOX_Y_OFFSET);
+            if refuge_transform
+                .translation()
+                .truncate()
+                .distance(hitbox_position)
+                <= LANDMARK_SENSOR_SIZE
+            {
+                // Hidden in the refuge, waiting for the threat to leave
+                (0., 0.)
+            } else {
+                move_to(refuge_transform, false, transform, speed)
+            }
+        }
+        None => match pos_query.get(*threat) {
+            Err(_) => (0., 0.),
+            Ok(threat_transform) => move_away(threat_transform, transform, speed),
+        },
+    }
+}
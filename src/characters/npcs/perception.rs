@@ -0,0 +1,256 @@
+//! Perception of the targets
+//!
+//! A `TargetSeeker` perceives a target
+//!
+//! - by sight: in its vision cone, facing where it goes,
+//!   and not hidden behind a wall (raycast through the `RapierContext`)
+//! - by hearing: the radius is larger if the target is running
+//!
+//! A `Hidden` target cannot be seen, a `Sneaking` one is heard as if idling.
+//! Only a perceived target can be chased.
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    animations::sprite_sheet_animation::CharacterState,
+    characters::{
+        npcs::{
+            aggression::EngagePursuitEvent,
+            flee::Fleer,
+            movement::{Chaser, TargetSeeker, TargetType},
+            suspicion::Suspicion,
+            NPC,
+        },
+        player::{Hidden, Player, Sneaking},
+    },
+    combat::{FairPlayTimer, Reputation},
+    constants::character::npcs::perception::*,
+    locations::temple::{Location, WallCollider},
+};
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, Copy, Reflect, Component)]
+pub struct Perception {
+    pub vision_range: f32,
+    /// In radians, from the facing direction to the edge of the cone.
+    pub vision_half_angle: f32,
+    /// Hears a running target in this radius.
+    pub hearing_radius_run: f32,
+    /// Hears an idling/walking target in this radius.
+    pub hearing_radius_idle: f32,
+}
+
+impl Default for Perception {
+    fn default() -> Self {
+        Perception {
+            vision_range: VISION_RANGE,
+            vision_half_angle: VISION_HALF_ANGLE,
+            hearing_radius_run: HEARING_RADIUS_RUN,
+            hearing_radius_idle: HEARING_RADIUS_IDLE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Sense {
+    Sight,
+    Hearing,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Events                                   */
+/* -------------------------------------------------------------------------- */
+
+/// Happens when:
+///   - npc::perception::perceive_targets
+///     - A target is seen or heard by a `TargetSeeker`
+///
+/// Read in
+///   - npc::perception::engage_perceived_target
+///   - npc::suspicion::suspicion_update
+#[derive(Event)]
+pub struct TargetPerceivedEvent {
+    pub npc_entity: Entity,
+    pub target_entity: Entity,
+    pub sense: Sense,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+/// Every free `TargetSeeker` looks and listens for its targets in its room.
+pub fn perceive_targets(
+    rapier_context: Res<RapierContext>,
+
+    seeker_query: Query<
+        (
+            Entity,
+            &Perception,
+            &TargetSeeker,
+            &GlobalTransform,
+            &Location,
+            &Reputation,
+            &Velocity,
+            &TextureAtlasSprite,
+        ),
+        (
+            With<NPC>,
+            Without<Chaser>,
+            Without<Fleer>,
+            Without<FairPlayTimer>,
+        ),
+    >,
+    character_query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &Location,
+            &Reputation,
+            &CharacterState,
+            Option<&Sneaking>,
+            Option<&Hidden>,
+        ),
+        Or<(With<NPC>, With<Player>)>,
+    >,
+    player_query: Query<Entity, With<Player>>,
+    wall_query: Query<(), With<WallCollider>>,
+    sensor_query: Query<(), With<Sensor>>,
+
+    mut ev_perceived: EventWriter<TargetPerceivedEvent>,
+) {
+    for (
+        npc,
+        perception,
+        TargetSeeker(target_type),
+        npc_transform,
+        npc_location,
+        npc_reputation,
+        rb_vel,
+        sprite,
+    ) in &seeker_query
+    {
+        let npc_position = npc_transform.translation().truncate();
+        let facing = if rb_vel.linvel != Vec2::ZERO {
+            rb_vel.linvel.normalize()
+        } else if sprite.flip_x {
+            Vec2::NEG_X
+        } else {
+            Vec2::X
+        };
+
+        for (
+            character,
+            transform,
+            location,
+            reputation,
+            character_state,
+            potential_sneaking,
+            potential_hidden,
+        ) in &character_query
+        {
+            if character == npc || location != npc_location {
+                continue;
+            }
+            let is_target_type = match target_type {
+                TargetType::Player => player_query.get(character).is_ok(),
+                TargetType::Enemy => !npc_reputation.in_the_same_team(reputation),
+                TargetType::Ally => npc_reputation.in_the_same_team(reputation),
+                TargetType::Special(target) => *target == character,
+            };
+            if !is_target_type {
+                continue;
+            }
+
+            let offset = transform.translation().truncate() - npc_position;
+            let distance = offset.length();
+
+            let hearing_radius = match (character_state, potential_sneaking) {
+                (CharacterState::Run, None) => perception.hearing_radius_run,
+                _ => perception.hearing_radius_idle,
+            };
+            let sense = if distance <= hearing_radius {
+                Sense::Hearing
+            } else if potential_hidden.is_none()
+                && distance <= perception.vision_range
+                && facing.angle_between(offset).abs() <= perception.vision_half_angle
+                && in_line_of_sight(
+                    &rapier_context,
+                    npc_position,
+                    offset,
+                    &wall_query,
+                    &sensor_query,
+                )
+            {
+                Sense::Sight
+            } else {
+                continue;
+            };
+
+            ev_perceived.send(TargetPerceivedEvent {
+                npc_entity: npc,
+                target_entity: character,
+                sense,
+            });
+        }
+    }
+}
+
+/// Nothing solid between the npc and the target.
+///
+/// The walls of the other rooms are turned into sensors
+/// (see `temple::control_wall_collider`) but still block the sight.
+fn in_line_of_sight(
+    rapier_context: &RapierContext,
+    origin: Vec2,
+    offset: Vec2,
+    wall_query: &Query<(), With<WallCollider>>,
+    sensor_query: &Query<(), With<Sensor>>,
+) -> bool {
+    let predicate =
+        |collider: Entity| wall_query.get(collider).is_ok() || sensor_query.get(collider).is_err();
+    let filter = QueryFilter::only_fixed().predicate(&predicate);
+
+    // with a non-normalized direction, a `toi` of 1. is the target position
+    rapier_context
+        .cast_ray(origin, offset, 1., true, filter)
+        .is_none()
+}
+
+/// A perceived target is instantly chased,
+/// unless the npc has to become `Suspicion`-ous first.
+pub fn engage_perceived_target(
+    mut ev_perceived: EventReader<TargetPerceivedEvent>,
+    name_query: Query<&Name>,
+    suspicion_query: Query<(), With<Suspicion>>,
+
+    mut ev_engage_pursuit: EventWriter<EngagePursuitEvent>,
+) {
+    let mut engaged_npcs = Vec::new();
+    for TargetPerceivedEvent {
+        npc_entity,
+        target_entity,
+        sense,
+    } in ev_perceived.iter()
+    {
+        if engaged_npcs.contains(npc_entity) || suspicion_query.get(*npc_entity).is_ok() {
+            continue;
+        }
+        engaged_npcs.push(*npc_entity);
+
+        ev_engage_pursuit.send(EngagePursuitEvent {
+            npc_entity: *npc_entity,
+            target_entity: *target_entity,
+        });
+        if let Ok([npc_name, target_name]) = name_query.get_many([*npc_entity, *target_entity]) {
+            info!(
+                "{} perceived {} ({:?}): chase initialized",
+                npc_name, target_name, sense
+            );
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! AI tuning profiles
+//!
+//! Named sets of sensor radii, speed, rest and fair-play times
+//! (e.g. "villager"), loaded from `data/ai_profiles.ron`.
+//!
+//! Each npc holds a copy of its profile.
+//! Editing the `AIProfiles` resource in the inspector updates all the npcs sharing a profile;
+//! editing the `AIProfile` of a npc only affects this one.
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::{Collider, Sensor};
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+use crate::{
+    characters::{
+        movement::{CharacterCloseSensor, Speed},
+        npcs::{
+            aggression::{DetectionRangeSensor, PursuitRangeSensor},
+            movement::FollowRangeSensor,
+            NPC,
+        },
+    },
+    constants::{
+        character::npcs::{
+            movement::{EVASION_TIMER, GATHERING_REST_TIMER, NPC_SPEED, REST_TIMER},
+            profile::*,
+        },
+        TILE_SIZE,
+    },
+    interactions::InteractionSensor,
+};
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Deserialize, Reflect, Debug, Clone, PartialEq, Component)]
+pub struct AIProfile {
+    pub name: String,
+    /// In tiles.
+    pub interaction_radius: f32,
+    pub follow_radius: f32,
+    pub detection_radius: f32,
+    pub pursuit_radius: f32,
+    pub close_radius: f32,
+    /// In tiles per second.
+    pub speed: f32,
+    /// Seconds of rest at a landmark.
+    pub rest_time: u64,
+    /// Seconds of rest at a landmark of a `LandmarkGroup`.
+    pub gathering_rest_time: u64,
+    /// Seconds before chasing again after a chase or a combat.
+    pub fair_play_time: u64,
+}
+
+impl Default for AIProfile {
+    fn default() -> Self {
+        AIProfile {
+            name: DEFAULT_AI_PROFILE.to_string(),
+            interaction_radius: INTERACTION_SENSOR_RADIUS,
+            follow_radius: FOLLOW_SENSOR_RADIUS,
+            detection_radius: DETECTION_SENSOR_RADIUS,
+            pursuit_radius: PURSUIT_SENSOR_RADIUS,
+            close_radius: CLOSE_SENSOR_RADIUS,
+            speed: NPC_SPEED / TILE_SIZE,
+            rest_time: REST_TIMER,
+            gathering_rest_time: GATHERING_REST_TIMER,
+            fair_play_time: EVASION_TIMER,
+        }
+    }
+}
+
+impl AIProfile {
+    /// Fair-play seconds of a npc, the default ones without profile.
+    pub fn fair_play_time_of(potential_profile: Option<&AIProfile>) -> u64 {
+        potential_profile.map_or(EVASION_TIMER, |profile| profile.fair_play_time)
+    }
+
+    /// Pursuit radius of a npc, in tiles, the default one without profile.
+    pub fn pursuit_radius_of(potential_profile: Option<&AIProfile>) -> f32 {
+        potential_profile.map_or(PURSUIT_SENSOR_RADIUS, |profile| profile.pursuit_radius)
+    }
+
+    /// Rest seconds of a npc at a landmark, the default ones without profile.
+    pub fn rest_time_of(potential_profile: Option<&AIProfile>, gathering: bool) -> u64 {
+        match (potential_profile, gathering) {
+            (Some(profile), true) => profile.gathering_rest_time,
+            (Some(profile), false) => profile.rest_time,
+            (None, true) => GATHERING_REST_TIMER,
+            (None, false) => REST_TIMER,
+        }
+    }
+}
+
+/// Content of the profiles file, kept as a resource.
+#[derive(Deserialize, Reflect, Debug, Default, Resource)]
+#[reflect(Resource)]
+pub struct AIProfiles {
+    pub profiles: Vec<AIProfile>,
+}
+
+impl AIProfiles {
+    /// The named profile, or the default one.
+    pub fn get(&self, name: &str) -> AIProfile {
+        match self.profiles.iter().find(|profile| profile.name == name) {
+            Some(profile) => profile.clone(),
+            None => {
+                warn!("Unknown AI profile {name}: default one");
+                AIProfile::default()
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AIProfilesError {
+    Io(std::io::Error),
+    Parse(ron::Error),
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+fn load_ai_profiles_file(path: &Path) -> Result<AIProfiles, AIProfilesError> {
+    let content = fs::read_to_string(path).map_err(AIProfilesError::Io)?;
+    ron::from_str(&content).map_err(AIProfilesError::Parse)
+}
+
+pub fn load_ai_profiles(mut commands: Commands) {
+    let ai_profiles = match load_ai_profiles_file(Path::new(AI_PROFILES_FILE)) {
+        Ok(ai_profiles) => ai_profiles,
+        Err(error) => {
+            error!("Invalid AI profiles {AI_PROFILES_FILE}: {error:?}");
+            AIProfiles::default()
+        }
+    };
+    commands.insert_resource(ai_profiles);
+}
+
+/// A profile edited in the `AIProfiles` is given to all npcs sharing it.
+pub fn share_ai_profiles(
+    ai_profiles: Res<AIProfiles>,
+    mut npc_query: Query<&mut AIProfile, With<NPC>>,
+) {
+    if !ai_profiles.is_changed() || ai_profiles.is_added() {
+        return;
+    }
+    for mut npc_profile in &mut npc_query {
+        if let Some(profile) = ai_profiles
+            .profiles
+            .iter()
+            .find(|profile| profile.name == npc_profile.name)
+        {
+            if *npc_profile != *profile {
+                *npc_profile = profile.clone();
+            }
+        }
+    }
+}
+
+/// Resizes the sensors and updates the speed of the npcs with a new profile.
+pub fn apply_ai_profile(
+    mut npc_query: Query<(&AIProfile, &mut Speed, &Children), (Changed<AIProfile>, With<NPC>)>,
+    mut sensor_query: Query<
+        (
+            &mut Collider,
+            Option<&InteractionSensor>,
+            Option<&FollowRangeSensor>,
+            Option<&DetectionRangeSensor>,
+            Option<&PursuitRangeSensor>,
+            Option<&CharacterCloseSensor>,
+        ),
+        With<Sensor>,
+    >,
+) {
+    for (profile, mut speed, children) in &mut npc_query {
+        speed.0 = profile.speed * TILE_SIZE;
+
+        for child in children {
+            let Ok((mut collider, interaction, follow, detection, pursuit, close)) =
+                sensor_query.get_mut(*child)
+            else {
+                continue;
+            };
+            let radius = match (interaction, follow, detection, pursuit, close) {
+                (Some(_), _, _, _, _) => profile.interaction_radius,
+                (_, Some(_), _, _, _) => profile.follow_radius,
+                (_, _, Some(_), _, _) => profile.detection_radius,
+                (_, _, _, Some(_), _) => profile.pursuit_radius,
+                (_, _, _, _, Some(_)) => profile.close_radius,
+                _ => continue,
+            };
+            *collider = Collider::ball(radius * TILE_SIZE);
+        }
+    }
+}
@@ -0,0 +1,583 @@
+//! NPC roster
+//!
+//! All npcs are described in the roster file (`data/npcs.ron`):
+//! sprite, stats, faction, starting behavior, sensors, dialog and schedule.
+//! Adding a npc is a content change.
+//!
+//! Npcs are spawned and despawned through `NPCCommandsExt`,
+//! which keeps the landmarks and dialogs in sync.
+
+use bevy::{
+    ecs::system::{Command, SystemState},
+    prelude::*,
+};
+use bevy_rapier2d::prelude::*;
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+};
+use yml_dialog::DialogNode;
+
+use crate::{
+    animations::{
+        sprite_sheet_animation::{AnimationIndices, CharacterState},
+        CharacterSpriteSheet, GlobalAnimationIndices,
+    },
+    characters::{
+        movement::{CharacterCloseSensor, MovementBundle, Speed},
+        npcs::{
+            aggression::{DetectionRangeSensor, PursuitRangeSensor, StopChaseEvent},
+            conversation::{Conversation, Speaker},
+            flee::{Fleer, StopFleeEvent},
+            memory::Memory,
+            movement::{Chaser, FollowRangeSensor, NPCBehavior, TargetSeeker, TargetType},
+            perception::Perception,
+            profile::{AIProfile, AIProfiles},
+            suspicion::Suspicion,
+            NPC,
+        },
+        CharacterHitbox,
+    },
+    combat::{Hp, Reputation},
+    constants::{
+        character::{
+            npcs::{profile::DEFAULT_AI_PROFILE, roster::*, NPC_SCALE},
+            CHAR_HITBOX_HEIGHT, CHAR_HITBOX_WIDTH, CHAR_HITBOX_Y_OFFSET, SPRITESHEET_COLUMN_NUMBER,
+            SPRITESHEET_LINE_NUMBER,
+        },
+        interactions::INTERACT_BUTTON_SCALE,
+        TILE_SIZE,
+    },
+    interactions::{InteractIcon, Interactible, InteractionResources, InteractionSensor},
+    locations::{
+        landmarks::{seek_requested_landmark, Landmark, LandmarkRequest, LandmarkStatus},
+        temple::Location,
+    },
+    ui::dialog_systems::{CurrentInterlocutor, DialogMap},
+    GameState, HUDState, StateScoped,
+};
+
+/* -------------------------------------------------------------------------- */
+/*                                 Definitions                                */
+/* -------------------------------------------------------------------------- */
+
+/// Content of the roster file.
+///
+/// Kept as a resource once loaded, to spawn more npcs later on.
+#[derive(Deserialize, Debug, Default, Resource)]
+pub struct NPCRoster {
+    pub npcs: Vec<NPCDefinition>,
+}
+
+impl NPCRoster {
+    pub fn get(&self, name: &str) -> Option<&NPCDefinition> {
+        self.npcs.iter().find(|definition| definition.name == name)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct NPCDefinition {
+    pub name: String,
+    /// Name displayed in the dialogs.
+    pub speaker: String,
+    /// Line of the character spritesheet.
+    pub sprite_line: usize,
+    /// Number of npcs spawned with this definition,
+    /// numbered after their name when more than one.
+    pub count: usize,
+    /// In tiles.
+    pub position: (f32, f32),
+    pub location: Location,
+    pub stats: NPCStats,
+    /// Name of its `AIProfile`, the default one if none.
+    #[serde(default)]
+    pub profile: Option<String>,
+    pub faction: Faction,
+    pub behavior: StartingBehavior,
+    /// Chases the player once sure about it (see `Suspicion`).
+    #[serde(default)]
+    pub hostile: bool,
+    #[serde(default)]
+    pub sensors: Vec<NPCSensor>,
+    /// Path of the yml dialog.
+    pub dialog: String,
+    /// Landmarks visited in turn, see `LandmarkSchedule`.
+    #[serde(default)]
+    pub schedule: Vec<LandmarkRequest>,
+    /// Extra states of the spritesheet, played as reactions.
+    #[serde(default)]
+    pub animations: Vec<AnimationDefinition>,
+}
+
+/// A `CharacterState` played once, then back to `Idle`
+/// (e.g. `Talk`, `Hurt`, `Attack`, see `characters::emotes`).
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct AnimationDefinition {
+    pub state: CharacterState,
+    /// Line of the character spritesheet, the `sprite_line` of the npc if none.
+    #[serde(default)]
+    pub line: Option<usize>,
+    /// First and last frames of the line.
+    pub columns: (usize, usize),
+}
+
+impl AnimationDefinition {
+    /// `None` if outside the spritesheet.
+    fn indices(&self, sprite_line: usize) -> Option<(usize, usize, CharacterState)> {
+        let line = self.line.unwrap_or(sprite_line);
+        let (first, last) = self.columns;
+        if line >= SPRITESHEET_LINE_NUMBER || first > last || last >= SPRITESHEET_COLUMN_NUMBER {
+            return None;
+        }
+        Some((
+            line * SPRITESHEET_COLUMN_NUMBER + first,
+            line * SPRITESHEET_COLUMN_NUMBER + last,
+            CharacterState::Idle,
+        ))
+    }
+}
+
+/// The speed is given by the `AIProfile`.
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct NPCStats {
+    pub hp: usize,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub enum Faction {
+    SupremeGod,
+    Olf,
+    Neutral,
+}
+
+impl From<Faction> for Reputation {
+    fn from(faction: Faction) -> Self {
+        match faction {
+            Faction::SupremeGod => Reputation::new(100, 0),
+            Faction::Olf => Reputation::new(0, 100),
+            Faction::Neutral => Reputation::new(0, 0),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub enum StartingBehavior {
+    Camping,
+    /// Reserves a landmark once spawned.
+    SeekLandmark,
+}
+
+/// Range sensors attached as children of the npc.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NPCSensor {
+    /// Used by the flee behavior
+    Pursuit,
+    Detection,
+    Follow,
+    Close,
+}
+
+#[derive(Debug)]
+pub enum NPCRosterError {
+    Io(std::io::Error),
+    Parse(ron::Error),
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+/// Landmarks visited in turn by a `NPCBehavior::LandmarkSeeking` npc.
+///
+/// When none matches the current request, any free landmark is taken.
+#[derive(Debug, Clone, Component)]
+pub struct LandmarkSchedule {
+    landmarks: Vec<LandmarkRequest>,
+    next: usize,
+}
+
+impl LandmarkSchedule {
+    pub fn new(landmarks: Vec<LandmarkRequest>) -> Self {
+        LandmarkSchedule { landmarks, next: 0 }
+    }
+
+    /// The current request, then moves on to the next one.
+    pub fn next_request(&mut self) -> Option<LandmarkRequest> {
+        let request = self.landmarks.get(self.next)?.clone();
+        self.next = (self.next + 1) % self.landmarks.len();
+        Some(request)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                  Commands                                  */
+/* -------------------------------------------------------------------------- */
+
+/// Spawns a single npc (whatever its `count`) from its definition.
+///
+/// The entity is reserved by `NPCCommandsExt::spawn_npc`
+/// and filled when the command is applied.
+pub struct SpawnNPC {
+    pub npc: Entity,
+    pub definition: NPCDefinition,
+}
+
+impl Command for SpawnNPC {
+    fn apply(self, world: &mut World) {
+        let mut system_state: SystemState<(
+            Commands,
+            Res<CharacterSpriteSheet>,
+            Res<GlobalAnimationIndices>,
+            Res<InteractionResources>,
+            Option<Res<AIProfiles>>,
+            ResMut<DialogMap>,
+            Query<(Entity, &mut Landmark), With<Sensor>>,
+        )> = SystemState::new(world);
+        let (
+            mut commands,
+            characters_spritesheet,
+            global_animations_indices,
+            interaction_resources,
+            potential_ai_profiles,
+            mut dialogs,
+            mut landmark_sensor_query,
+        ) = system_state.get_mut(world);
+
+        let SpawnNPC { npc, definition } = self;
+        if definition.sprite_line >= global_animations_indices.len() {
+            error!(
+                "{}: no spritesheet line {}",
+                definition.name, definition.sprite_line
+            );
+            commands.entity(npc).despawn();
+        } else {
+            let profile_name = definition.profile.as_deref().unwrap_or(DEFAULT_AI_PROFILE);
+            let profile = match &potential_ai_profiles {
+                Some(ai_profiles) => ai_profiles.get(profile_name),
+                None => AIProfile::default(),
+            };
+            build_npc(
+                &mut commands,
+                npc,
+                &characters_spritesheet,
+                &global_animations_indices,
+                &interaction_resources,
+                &definition,
+                profile,
+            );
+
+            // A landmark is reserved in the name of the npc
+            let mut schedule = LandmarkSchedule::new(definition.schedule.clone());
+            let behavior = match definition.behavior {
+                StartingBehavior::Camping => NPCBehavior::Camping,
+                StartingBehavior::SeekLandmark => seek_requested_landmark(
+                    &mut landmark_sensor_query,
+                    definition.location,
+                    &schedule.next_request().unwrap_or(LandmarkRequest::Any),
+                    npc,
+                ),
+            };
+            commands.entity(npc).insert(behavior);
+            if !definition.schedule.is_empty() {
+                commands.entity(npc).insert(schedule);
+            }
+
+            if let Some(dialog) = load_dialog(&definition.dialog) {
+                dialogs.insert(npc, dialog);
+            }
+        }
+
+        system_state.apply(world);
+    }
+}
+
+/// Despawns a npc and cleans everything pointing to it:
+///
+/// - its reserved or occupied landmarks are freed
+/// - its dialog is removed from the `DialogMap`
+/// - the dialog with it, if any, is closed
+/// - the npcs chasing, fleeing or following it stop, and it leaves its conversation
+pub struct DespawnNPC(pub Entity);
+
+impl Command for DespawnNPC {
+    fn apply(self, world: &mut World) {
+        let DespawnNPC(npc) = self;
+
+        let mut landmark_sensor_query = world.query_filtered::<&mut Landmark, With<Sensor>>();
+        for mut landmark in landmark_sensor_query.iter_mut(world) {
+            if landmark.status == LandmarkStatus::ReservedBy(npc)
+                || landmark.status == LandmarkStatus::OccupiedBy(npc)
+            {
+                landmark.status = LandmarkStatus::Free;
+            }
+        }
+
+        if let Some(mut dialogs) = world.get_resource_mut::<DialogMap>() {
+            dialogs.remove(&npc);
+        }
+
+        let mut chaser_query = world.query::<(Entity, &Chaser)>();
+        let chasers: Vec<Entity> = chaser_query
+            .iter(world)
+            .filter(|(_, chaser)| chaser.target == npc)
+            .map(|(chaser, _)| chaser)
+            .collect();
+        for chaser in chasers {
+            world.entity_mut(chaser).remove::<Chaser>();
+            // Restores the detection of the chaser
+            world.send_event(StopChaseEvent { npc_entity: chaser });
+        }
+
+        let mut fleer_query = world.query::<(Entity, &Fleer)>();
+        let fleers: Vec<Entity> = fleer_query
+            .iter(world)
+            .filter(|(_, fleer)| fleer.threat == npc)
+            .map(|(fleer, _)| fleer)
+            .collect();
+        for fleer in fleers {
+            // Removes the `Fleer` and restores the sensors of the fleer
+            world.send_event(StopFleeEvent { npc_entity: fleer });
+        }
+
+        let mut behavior_query = world.query::<&mut NPCBehavior>();
+        for mut behavior in behavior_query.iter_mut(world) {
+            if matches!(*behavior, NPCBehavior::Follow { target, .. } if target == npc) {
+                *behavior = NPCBehavior::Camping;
+            }
+        }
+
+        let mut conversation_query = world.query::<&mut Conversation>();
+        for mut conversation in conversation_query.iter_mut(world) {
+            conversation.members.retain(|member| *member != npc);
+            conversation.roles.retain(|_, member| *member != npc);
+        }
+
+        let was_interlocutor = match world.get_resource_mut::<CurrentInterlocutor>() {
+            Some(mut current_interlocutor) if current_interlocutor.interlocutor == Some(npc) => {
+                current_interlocutor.interlocutor = None;
+                true
+            }
+            _ => false,
+        };
+        if was_interlocutor {
+            if let Some(mut next_hud_state) = world.get_resource_mut::<NextState<HUDState>>() {
+                next_hud_state.set(HUDState::Closed);
+            }
+        }
+
+        match world.get_entity_mut(npc) {
+            Some(npc) => npc.despawn_recursive(),
+            None => warn!("Despawn of a missing npc {npc:?}"),
+        }
+    }
+}
+
+pub trait NPCCommandsExt {
+    /// Spawns a npc at any time (e.g. combat reinforcements, scripted events).
+    ///
+    /// The returned entity is filled when the commands are applied.
+    fn spawn_npc(&mut self, definition: NPCDefinition) -> Entity;
+    fn despawn_npc(&mut self, npc: Entity);
+}
+
+impl NPCCommandsExt for Commands<'_, '_> {
+    fn spawn_npc(&mut self, definition: NPCDefinition) -> Entity {
+        let npc = self.spawn_empty().id();
+        self.add(SpawnNPC { npc, definition });
+        npc
+    }
+
+    fn despawn_npc(&mut self, npc: Entity) {
+        self.add(DespawnNPC(npc));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+fn load_roster_file(path: &Path) -> Result<NPCRoster, NPCRosterError> {
+    let content = fs::read_to_string(path).map_err(NPCRosterError::Io)?;
+    ron::from_str(&content).map_err(NPCRosterError::Parse)
+}
+
+/// Spawns every npc of the roster file,
+/// then keeps the roster for the npcs spawned later on.
+pub fn spawn_roster(mut commands: Commands) {
+    let roster = match load_roster_file(Path::new(NPC_ROSTER_FILE)) {
+        Ok(roster) => roster,
+        Err(error) => {
+            error!("Invalid npc roster {NPC_ROSTER_FILE}: {error:?}");
+            NPCRoster::default()
+        }
+    };
+
+    for definition in &roster.npcs {
+        for i in 0..definition.count {
+            let mut npc_definition = definition.clone();
+            if definition.count > 1 {
+                npc_definition.name = format!("{} {}", definition.name, i + 1);
+            }
+            commands.spawn_npc(npc_definition);
+        }
+    }
+
+    commands.insert_resource(roster);
+}
+
+/// The npc with its children, without any behavior.
+fn build_npc(
+    commands: &mut Commands,
+    npc: Entity,
+    characters_spritesheet: &CharacterSpriteSheet,
+    global_animations_indices: &GlobalAnimationIndices,
+    interaction_resources: &InteractionResources,
+    definition: &NPCDefinition,
+    profile: AIProfile,
+) {
+    let name = &definition.name;
+    let mut npc_animation_indices = AnimationIndices(HashMap::new());
+    npc_animation_indices.insert(
+        CharacterState::Run,
+        global_animations_indices[definition.sprite_line][0],
+    );
+    npc_animation_indices.insert(
+        CharacterState::Idle,
+        global_animations_indices[definition.sprite_line][1],
+    );
+    for animation in &definition.animations {
+        match animation.indices(definition.sprite_line) {
+            Some(indices) => {
+                npc_animation_indices.insert(animation.state, indices);
+            }
+            None => error!("{}: invalid {:?} animation", name, animation.state),
+        }
+    }
+
+    let interactible = Interactible::new_npc();
+    let (x, y) = definition.position;
+
+    let mut npc = commands.entity(npc);
+    npc.insert((
+        SpriteSheetBundle {
+            texture_atlas: characters_spritesheet.texture_atlas.clone(),
+            transform: Transform {
+                translation: Vec3::new(x * TILE_SIZE, y * TILE_SIZE, 0.),
+                scale: Vec3::splat(NPC_SCALE),
+                ..default()
+            },
+            ..default()
+        },
+        Name::new(format!("NPC {}", name)),
+        StateScoped(GameState::Playing),
+        NPC,
+        // -- Movement --
+        MovementBundle {
+            speed: Speed(profile.speed * TILE_SIZE),
+            animation_indices: npc_animation_indices,
+            ..default()
+        },
+        definition.location,
+        // -- Social --
+        interactible,
+        Reputation::from(definition.faction),
+        Speaker(definition.speaker.clone()),
+        Hp::new(definition.stats.hp),
+        Memory::default(),
+        // -- Hitbox --
+        RigidBody::Dynamic,
+        LockedAxes::ROTATION_LOCKED,
+    ));
+    if definition.hostile {
+        npc.insert((
+            TargetSeeker(TargetType::Player),
+            Perception::default(),
+            Suspicion::default(),
+        ));
+    }
+
+    npc.with_children(|parent| {
+        // The interaction sensor must be the first child
+        parent.spawn((
+            Collider::ball(profile.interaction_radius * TILE_SIZE),
+            Transform::IDENTITY,
+            Sensor,
+            InteractionSensor,
+            Name::new(format!("{} Interaction Sensor", name)),
+        ));
+
+        parent.spawn((
+            SpriteBundle {
+                texture: interaction_resources.interact_button.clone(),
+                transform: Transform {
+                    translation: interactible.icon_translation,
+                    scale: Vec3::splat(INTERACT_BUTTON_SCALE),
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            InteractIcon,
+        ));
+
+        parent.spawn((
+            Collider::cuboid(CHAR_HITBOX_WIDTH, CHAR_HITBOX_HEIGHT),
+            Transform::from_xyz(0., CHAR_HITBOX_Y_OFFSET, 0.),
+            CharacterHitbox,
+            Name::new(format!("{} Hitbox", name)),
+        ));
+
+        for sensor in &definition.sensors {
+            match sensor {
+                NPCSensor::Pursuit => parent.spawn((
+                    Collider::ball(profile.pursuit_radius * TILE_SIZE),
+                    Sensor,
+                    PursuitRangeSensor,
+                    Name::new(format!("{} Pursuit Range", name)),
+                )),
+                NPCSensor::Detection => parent.spawn((
+                    Collider::ball(profile.detection_radius * TILE_SIZE),
+                    Sensor,
+                    DetectionRangeSensor,
+                    Name::new(format!("{} Detection Range", name)),
+                )),
+                NPCSensor::Follow => parent.spawn((
+                    Collider::ball(profile.follow_radius * TILE_SIZE),
+                    Sensor,
+                    FollowRangeSensor,
+                    Name::new(format!("{} Follow Range", name)),
+                )),
+                NPCSensor::Close => parent.spawn((
+                    Collider::ball(profile.close_radius * TILE_SIZE),
+                    Sensor,
+                    ActiveEvents::COLLISION_EVENTS,
+                    ActiveCollisionTypes::STATIC_STATIC,
+                    CharacterCloseSensor,
+                    Name::new(format!("{} Close Sensor", name)),
+                )),
+            };
+        }
+    });
+
+    npc.insert(profile);
+}
+
+/// The first node and the whole dialog tree, as stored in the `DialogMap`.
+fn load_dialog(path: &str) -> Option<(usize, BTreeMap<usize, DialogNode>)> {
+    let dialog_file = match fs::File::open(path) {
+        Ok(dialog_file) => dialog_file,
+        Err(error) => {
+            error!("Missing dialog {path}: {error:?}");
+            return None;
+        }
+    };
+    match serde_yaml::from_reader::<_, BTreeMap<usize, DialogNode>>(dialog_file) {
+        Ok(dialog) => Some((*dialog.first_key_value()?.0, dialog)),
+        Err(error) => {
+            error!("Invalid dialog {path}: {error:?}");
+            None
+        }
+    }
+}
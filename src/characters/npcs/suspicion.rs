@@ -0,0 +1,195 @@
+//! Suspicion of the npcs
+//!
+//! Instead of instantly chasing a perceived target,
+//! a suspicious npc fills up a meter above its head.
+//! Seeing the target fills it slower than hearing it,
+//! and an `Alert`-ed npc, or one remembering the target attacked it, fills it faster;
+//! out of sight and earshot, the meter slowly empties.
+//!
+//! When the meter is full, the chase begins.
+
+use bevy::{prelude::*, sprite::Anchor};
+
+use crate::{
+    characters::npcs::{
+        aggression::EngagePursuitEvent,
+        alert::Alert,
+        memory::{Memory, MemoryKind},
+        perception::{Sense, TargetPerceivedEvent},
+    },
+    constants::character::npcs::{memory::ATTACKER_SUSPICION_FACTOR, suspicion::*},
+};
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Default, Reflect, Component)]
+pub struct Suspicion {
+    /// From 0. (calm) to 1. (engages the target)
+    pub level: f32,
+    /// The last perceived target.
+    pub target: Option<Entity>,
+}
+
+/// Child of the npc, contains the `SuspicionMeterFill`.
+#[derive(Component)]
+pub struct SuspicionMeter;
+
+#[derive(Component)]
+pub struct SuspicionMeterFill;
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+/// Raises the suspicion of the npcs perceiving a target
+/// and lowers the others'.
+///
+/// A full suspicion engages the pursuit.
+pub fn suspicion_update(
+    time: Res<Time>,
+    mut ev_perceived: EventReader<TargetPerceivedEvent>,
+
+    mut suspicion_query: Query<(
+        Entity,
+        &mut Suspicion,
+        &Name,
+        Option<&Alert>,
+        Option<&Memory>,
+    )>,
+
+    mut ev_engage_pursuit: EventWriter<EngagePursuitEvent>,
+) {
+    let delta = time.delta_seconds();
+
+    // keeps the most alarming sense for each npc
+    let mut perceived: Vec<(Entity, Entity, f32)> = Vec::new();
+    for TargetPerceivedEvent {
+        npc_entity,
+        target_entity,
+        sense,
+    } in ev_perceived.iter()
+    {
+        let rate = match sense {
+            Sense::Sight => SUSPICION_SIGHT_RATE,
+            Sense::Hearing => SUSPICION_HEARING_RATE,
+        };
+        match perceived.iter_mut().find(|(npc, _, _)| npc == npc_entity) {
+            Some((_, target, best_rate)) => {
+                if rate > *best_rate {
+                    *target = *target_entity;
+                    *best_rate = rate;
+                }
+            }
+            None => perceived.push((*npc_entity, *target_entity, rate)),
+        }
+    }
+
+    for (npc, mut suspicion, name, potential_alert, potential_memory) in &mut suspicion_query {
+        match perceived.iter().find(|(perceiver, _, _)| *perceiver == npc) {
+            Some((_, target, rate)) => {
+                let alert_bonus = potential_alert.map_or(1., |alert| 1. + alert.level);
+                let grudge_bonus = match potential_memory {
+                    Some(memory) if memory.remembers_about(MemoryKind::Attacked, *target) => {
+                        ATTACKER_SUSPICION_FACTOR
+                    }
+                    _ => 1.,
+                };
+                suspicion.level =
+                    (suspicion.level + rate * alert_bonus * grudge_bonus * delta).min(1.);
+                suspicion.target = Some(*target);
+            }
+            None => {
+                if suspicion.level > 0. {
+                    suspicion.level = (suspicion.level - SUSPICION_DECAY_RATE * delta).max(0.);
+                }
+                continue;
+            }
+        }
+
+        if suspicion.level >= 1. {
+            if let Some(target) = suspicion.target {
+                info!("{} is sure: chase initialized", name);
+                ev_engage_pursuit.send(EngagePursuitEvent {
+                    npc_entity: npc,
+                    target_entity: target,
+                });
+            }
+            suspicion.level = 0.;
+        }
+    }
+}
+
+pub fn spawn_suspicion_meter(
+    mut commands: Commands,
+    suspicion_query: Query<Entity, Added<Suspicion>>,
+) {
+    for npc in &suspicion_query {
+        let (width, height) = SUSPICION_METER_SIZE;
+
+        let meter = commands
+            .spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::rgba(0., 0., 0., 0.6),
+                        custom_size: Some(Vec2::new(width, height)),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(SUSPICION_METER_TRANSLATION.into()),
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                SuspicionMeter,
+                Name::new("Suspicion Meter"),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::YELLOW,
+                            custom_size: Some(Vec2::new(0., height)),
+                            anchor: Anchor::CenterLeft,
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(-width / 2., 0., 0.1),
+                        ..default()
+                    },
+                    SuspicionMeterFill,
+                    Name::new("Suspicion Meter Fill"),
+                ));
+            })
+            .id();
+
+        commands.entity(npc).add_child(meter);
+    }
+}
+
+/// Fills the meter from yellow to red, hides it when calm.
+pub fn update_suspicion_meter(
+    suspicion_query: Query<(&Suspicion, &Children), Changed<Suspicion>>,
+    mut meter_query: Query<(&mut Visibility, &Children), With<SuspicionMeter>>,
+    mut fill_query: Query<&mut Sprite, With<SuspicionMeterFill>>,
+) {
+    let (width, height) = SUSPICION_METER_SIZE;
+
+    for (suspicion, children) in &suspicion_query {
+        for child in children {
+            let Ok((mut visibility, meter_children)) = meter_query.get_mut(*child) else {
+                continue;
+            };
+            *visibility = if suspicion.level > 0. {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+
+            for fill in meter_children {
+                if let Ok(mut sprite) = fill_query.get_mut(*fill) {
+                    sprite.custom_size = Some(Vec2::new(width * suspicion.level, height));
+                    sprite.color = Color::rgb(1., 1. - suspicion.level, 0.);
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
-use std::collections::{BTreeMap, HashMap};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
 use yml_dialog::DialogNode;
 
 use crate::{
@@ -10,15 +13,20 @@ use crate::{
     },
     characters::{
         movement::{MovementBundle, Speed},
+        stamina::Stamina,
         CharacterHitbox,
     },
     combat::{Leader, Reputation},
     constants::character::{player::*, *},
-    controls::KeyBindings,
+    controls::{Action, ActionInput},
     hud_closed,
-    locations::temple::Location,
+    locations::{
+        landmarks::{Landmark, LandmarkStatus, LandmarkTag},
+        temple::Location,
+    },
+    paused,
     ui::dialog_systems::DialogMap,
-    GameState, PlayerCamera,
+    GameState, PlayerCamera, StateScoped,
 };
 
 use super::movement::CharacterCloseSensor;
@@ -28,7 +36,19 @@ pub struct PlayerPlugin;
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::Playing), spawn_player)
-            .add_systems(Update, (player_movement.run_if(hud_closed), camera_follow));
+            .add_systems(
+                Update,
+                (
+                    (player_movement, player_dash)
+                        .chain()
+                        .run_if(hud_closed)
+                        .run_if(not(paused)),
+                    camera_follow,
+                    hide_in_landmarks,
+                    wear_off_invulnerability,
+                    animate_braking_dust,
+                ),
+            );
     }
 }
 
@@ -38,79 +58,297 @@ pub struct Player;
 #[derive(Component)]
 pub struct PlayerHitbox;
 
+/// Slower and quieter: heard only as if idling.
+#[derive(Component)]
+pub struct Sneaking;
+
+/// Occupying a landmark tagged `LandmarkTag::Hide`: cannot be seen.
+#[derive(Component)]
+pub struct Hidden;
+
 #[derive(Component)]
 struct Immobilized;
 
+/// Moving in a fixed direction, whatever the inputs.
+#[derive(Component)]
+pub struct Dashing {
+    pub direction: Vec2,
+    pub timer: Timer,
+}
+
+/// Restarted at the start of each dash.
+#[derive(Deref, DerefMut, Component)]
+pub struct DashCooldown(pub Timer);
+
+impl Default for DashCooldown {
+    /// Ready to dash.
+    fn default() -> Self {
+        let mut timer = Timer::from_seconds(DASH_COOLDOWN, TimerMode::Once);
+        timer.tick(Duration::from_secs_f32(DASH_COOLDOWN));
+        DashCooldown(timer)
+    }
+}
+
+/// Cannot be caught by a chaser (i-frames), until the timer is finished.
+#[derive(Deref, DerefMut, Component)]
+pub struct Invulnerable(pub Timer);
+
+/// Seconds spent running without stopping (nor sneaking).
+#[derive(Default, Deref, DerefMut, Component)]
+pub struct RunningTime(pub f32);
+
+/// A puff of dust, growing and fading until the timer is finished.
+#[derive(Deref, DerefMut, Component)]
+pub struct BrakingDust(pub Timer);
+
 #[derive(Component)]
 pub struct PlayerInteractionSensor;
 
 #[derive(Component)]
 pub struct PlayerCloseSensor;
 
+/// Sprinting spends stamina; sneaking prevents it.
+///
 /// FIXME: Freeze the player when in dialog (trigger for ex when interacting while running)
 fn player_movement(
-    key_bindings: Res<KeyBindings>,
-    keyboard_input: Res<Input<KeyCode>>,
+    mut commands: Commands,
+    time: Res<Time>,
+    action_input: ActionInput,
     mut player_query: Query<
         (
             Entity,
             &Speed,
+            &Transform,
             &mut Velocity,
             &mut TextureAtlasSprite,
             &mut CharacterState,
+            &mut Stamina,
+            &mut RunningTime,
+            Option<&Sneaking>,
         ),
-        With<Player>,
+        (With<Player>, Without<Dashing>),
     >,
 ) {
-    if let Ok((_player, speed, mut rb_vel, mut texture_atlas_sprite, mut player_state)) =
-        player_query.get_single_mut()
+    if let Ok((
+        player,
+        speed,
+        transform,
+        mut rb_vel,
+        mut texture_atlas_sprite,
+        mut player_state,
+        mut stamina,
+        mut running_time,
+        potential_sneaking,
+    )) = player_query.get_single_mut()
     {
-        let up = keyboard_input.any_pressed(key_bindings.up());
-        let down = keyboard_input.any_pressed(key_bindings.down());
-        let left = keyboard_input.any_pressed(key_bindings.left());
-        let right = keyboard_input.any_pressed(key_bindings.right());
-
-        let x_axis = -(left as i8) + right as i8;
-        let y_axis = -(down as i8) + up as i8;
+        let direction = action_input.movement();
+        let moving = direction != Vec2::ZERO;
+        let sneak = action_input.pressed(Action::Sneak);
+        let sprint =
+            !sneak && moving && action_input.pressed(Action::Sprint) && !stamina.is_exhausted();
 
-        let mut vel_x = x_axis as f32 * **speed;
-        let mut vel_y = y_axis as f32 * **speed;
-
-        if x_axis != 0 && y_axis != 0 {
-            vel_x *= (std::f32::consts::PI / 4.).cos();
-            vel_y *= (std::f32::consts::PI / 4.).cos();
+        if sneak && potential_sneaking.is_none() {
+            commands.entity(player).insert(Sneaking);
+        } else if !sneak && potential_sneaking.is_some() {
+            commands.entity(player).remove::<Sneaking>();
         }
+        let speed = if sneak {
+            **speed * SNEAK_SPEED_FACTOR
+        } else if sprint {
+            stamina.drain(SPRINT_STAMINA_PER_S * time.delta_seconds());
+            **speed * SPRINT_SPEED_FACTOR
+        } else {
+            **speed
+        };
 
-        // rb_vel.linvel.x = x_axis as f32 * **speed * 200. * time.delta_seconds();
-        rb_vel.linvel.x = vel_x;
-        rb_vel.linvel.y = vel_y;
+        // Already normalized on diagonals; analog sticks give slower speeds
+        rb_vel.linvel = direction * speed;
 
         /* -------------------------------------------------------------------------- */
         /*                                  Animation                                 */
         /* -------------------------------------------------------------------------- */
 
         // if there is any movement
-        if (left || right || up || down) && *player_state != CharacterState::Run {
+        if moving && *player_state != CharacterState::Run {
             *player_state = CharacterState::Run;
-        } else if !(left || right || up || down)
+        } else if !moving
             && *player_state == CharacterState::Run
             && *player_state != CharacterState::Idle
         {
-            // IDEA: Polish #visual - When we reach max speed (one full run loop), whenever you stop there is a smoke anim (sudden braking)
+            // Sudden braking after one full run loop
+            if **running_time >= BRAKING_DUST_RUN_TIME {
+                spawn_braking_dust(&mut commands, transform.translation);
+            }
             *player_state = CharacterState::Idle;
         }
 
+        if moving && !sneak {
+            **running_time += time.delta_seconds();
+        } else {
+            **running_time = 0.;
+        }
+
         /* -------------------------------------------------------------------------- */
         /*                                  Direction                                 */
         /* -------------------------------------------------------------------------- */
 
-        if !(left && right) {
-            if right {
-                texture_atlas_sprite.flip_x = false;
-            } else if left {
-                texture_atlas_sprite.flip_x = true;
-            }
+        if direction.x > 0. {
+            texture_atlas_sprite.flip_x = false;
+        } else if direction.x < 0. {
+            texture_atlas_sprite.flip_x = true;
+        }
+    }
+}
+
+/// Dashes towards the movement, or forward when not moving,
+/// if the cooldown is finished and there is enough stamina.
+/// The player is `Invulnerable` during the dash.
+fn player_dash(
+    mut commands: Commands,
+    time: Res<Time>,
+    action_input: ActionInput,
+    mut player_query: Query<
+        (
+            Entity,
+            &Speed,
+            &mut Velocity,
+            &TextureAtlasSprite,
+            &mut CharacterState,
+            &mut Stamina,
+            &mut DashCooldown,
+            Option<&mut Dashing>,
+        ),
+        With<Player>,
+    >,
+) {
+    let Ok((
+        player,
+        speed,
+        mut rb_vel,
+        texture_atlas_sprite,
+        mut player_state,
+        mut stamina,
+        mut dash_cooldown,
+        potential_dashing,
+    )) = player_query.get_single_mut()
+    else {
+        return;
+    };
+    dash_cooldown.tick(time.delta());
+
+    if let Some(mut dashing) = potential_dashing {
+        dashing.timer.tick(time.delta());
+        if dashing.timer.finished() {
+            commands.entity(player).remove::<Dashing>();
+        } else {
+            rb_vel.linvel = dashing.direction * **speed * DASH_SPEED_FACTOR;
         }
+        return;
+    }
+
+    if !action_input.just_pressed(Action::Dash)
+        || !dash_cooldown.finished()
+        || !stamina.try_spend(DASH_STAMINA_COST)
+    {
+        return;
+    }
+
+    let movement = action_input.movement();
+    let direction = if movement != Vec2::ZERO {
+        movement.normalize()
+    } else if texture_atlas_sprite.flip_x {
+        Vec2::NEG_X
+    } else {
+        Vec2::X
+    };
+
+    dash_cooldown.reset();
+    rb_vel.linvel = direction * **speed * DASH_SPEED_FACTOR;
+    *player_state = CharacterState::Run;
+    commands.entity(player).insert((
+        Dashing {
+            direction,
+            timer: Timer::from_seconds(DASH_DURATION, TimerMode::Once),
+        },
+        Invulnerable(Timer::from_seconds(
+            DASH_INVULNERABILITY_DURATION,
+            TimerMode::Once,
+        )),
+    ));
+}
+
+fn wear_off_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut invulnerable_query: Query<(Entity, &mut Invulnerable)>,
+) {
+    for (character, mut invulnerable) in &mut invulnerable_query {
+        invulnerable.tick(time.delta());
+        if invulnerable.finished() {
+            commands.entity(character).remove::<Invulnerable>();
+        }
+    }
+}
+
+/// Two puffs, on each side of the feet.
+fn spawn_braking_dust(commands: &mut Commands, player_translation: Vec3) {
+    let (offset_x, offset_y) = BRAKING_DUST_OFFSET;
+    for side in [-1., 1.] {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: BRAKING_DUST_COLOR,
+                    custom_size: Some(Vec2::splat(BRAKING_DUST_SIZE)),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    player_translation + Vec3::new(side * offset_x, offset_y, 0.) * PLAYER_SCALE,
+                ),
+                ..default()
+            },
+            BrakingDust(Timer::from_seconds(BRAKING_DUST_DURATION, TimerMode::Once)),
+            StateScoped(GameState::Playing),
+            Name::new("Braking Dust"),
+        ));
+    }
+}
+
+fn animate_braking_dust(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut dust_query: Query<(Entity, &mut BrakingDust, &mut Sprite)>,
+) {
+    for (dust, mut timer, mut sprite) in &mut dust_query {
+        timer.tick(time.delta());
+        let t = timer.percent();
+        sprite.custom_size = Some(Vec2::splat(
+            BRAKING_DUST_SIZE * (1. + (BRAKING_DUST_GROWTH - 1.) * t),
+        ));
+        sprite.color.set_a(BRAKING_DUST_COLOR.a() * (1. - t));
+        if timer.finished() {
+            commands.entity(dust).despawn();
+        }
+    }
+}
+
+/// The player is `Hidden` while occupying a hiding spot.
+fn hide_in_landmarks(
+    mut commands: Commands,
+    player_query: Query<(Entity, Option<&Hidden>), With<Player>>,
+    landmark_sensor_query: Query<&Landmark, With<Sensor>>,
+) {
+    let Ok((player, potential_hidden)) = player_query.get_single() else {
+        return;
+    };
+
+    let in_hiding_spot = landmark_sensor_query.iter().any(|landmark| {
+        landmark.status == LandmarkStatus::OccupiedBy(player)
+            && landmark.tags.contains(&LandmarkTag::Hide)
+    });
+    if in_hiding_spot && potential_hidden.is_none() {
+        commands.entity(player).insert(Hidden);
+    } else if !in_hiding_spot && potential_hidden.is_some() {
+        commands.entity(player).remove::<Hidden>();
     }
 }
 
@@ -165,8 +403,12 @@ fn spawn_player(
                 ..default()
             },
             Name::new("Player"),
+            StateScoped(GameState::Playing),
             Player,
             Location::default(),
+            Stamina::default(),
+            DashCooldown::default(),
+            RunningTime::default(),
             // -- Social --
             Reputation::new(100, 0),
             Leader,
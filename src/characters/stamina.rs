@@ -0,0 +1,154 @@
+//! Stamina
+//!
+//! Spent by sprinting and dashing (see `player::player_movement` and `player::player_dash`),
+//! regenerated after `STAMINA_REGEN_DELAY` without spending any.
+//! The stamina of the player is drawn in the bottom left corner.
+
+use bevy::prelude::*;
+
+use crate::{
+    characters::player::Player,
+    constants::{
+        character::player::{
+            DASH_STAMINA_COST, MAX_STAMINA, STAMINA_REGEN_DELAY, STAMINA_REGEN_PER_S,
+        },
+        ui::stamina::*,
+    },
+    GameState, StateScoped,
+};
+
+pub struct StaminaPlugin;
+
+impl Plugin for StaminaPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), spawn_stamina_bar)
+            .add_systems(
+                Update,
+                (regenerate_stamina, update_stamina_bar)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug, Reflect, Component)]
+pub struct Stamina {
+    pub current: f32,
+    pub max: f32,
+    /// Restarted each time some stamina is spent.
+    pub regen_delay: Timer,
+}
+
+impl Default for Stamina {
+    fn default() -> Self {
+        Stamina {
+            current: MAX_STAMINA,
+            max: MAX_STAMINA,
+            regen_delay: Timer::from_seconds(STAMINA_REGEN_DELAY, TimerMode::Once),
+        }
+    }
+}
+
+impl Stamina {
+    /// Spends the whole `amount`, if there is enough stamina.
+    pub fn try_spend(&mut self, amount: f32) -> bool {
+        if self.current < amount {
+            return false;
+        }
+        self.drain(amount);
+        true
+    }
+
+    /// Spends up to `amount`.
+    pub fn drain(&mut self, amount: f32) {
+        self.current = (self.current - amount).max(0.);
+        self.regen_delay.reset();
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.current <= 0.
+    }
+}
+
+#[derive(Component)]
+pub struct StaminaBar;
+
+#[derive(Component)]
+pub struct StaminaBarFill;
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+pub fn regenerate_stamina(time: Res<Time>, mut stamina_query: Query<&mut Stamina>) {
+    for mut stamina in &mut stamina_query {
+        if !stamina.regen_delay.finished() {
+            stamina.regen_delay.tick(time.delta());
+        } else if stamina.current < stamina.max {
+            stamina.current =
+                (stamina.current + STAMINA_REGEN_PER_S * time.delta_seconds()).min(stamina.max);
+        }
+    }
+}
+
+fn spawn_stamina_bar(mut commands: Commands) {
+    let (width, height) = STAMINA_BAR_SIZE;
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Px(width),
+                    height: Val::Px(height),
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(STAMINA_BAR_MARGIN),
+                    bottom: Val::Px(STAMINA_BAR_MARGIN),
+                    ..default()
+                },
+                background_color: STAMINA_BAR_BACKGROUND.into(),
+                ..default()
+            },
+            StaminaBar,
+            StateScoped(GameState::Playing),
+            Name::new("Stamina Bar"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..default()
+                    },
+                    background_color: STAMINA_BAR_FILL.into(),
+                    ..default()
+                },
+                StaminaBarFill,
+                Name::new("Stamina Bar Fill"),
+            ));
+        });
+}
+
+/// Red while too low to dash.
+fn update_stamina_bar(
+    player_query: Query<&Stamina, (With<Player>, Changed<Stamina>)>,
+    mut fill_query: Query<(&mut Style, &mut BackgroundColor), With<StaminaBarFill>>,
+) {
+    let Ok(stamina) = player_query.get_single() else {
+        return;
+    };
+    let Ok((mut style, mut color)) = fill_query.get_single_mut() else {
+        return;
+    };
+
+    style.width = Val::Percent(100. * stamina.current / stamina.max);
+    *color = if stamina.current < DASH_STAMINA_COST {
+        STAMINA_BAR_LOW_FILL.into()
+    } else {
+        STAMINA_BAR_FILL.into()
+    };
+}
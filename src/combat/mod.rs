@@ -32,14 +32,15 @@
 
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::Velocity;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use crate::{
     characters::{
-        npcs::{NPCSystems, NPC},
+        npcs::{profile::AIProfile, NPCSystems, NPC},
         player::Player,
     },
-    constants::character::npcs::movement::EVASION_TIMER,
+    constants::character::npcs::DAMAGE_PER_PARTY_MEMBER,
     HUDState,
 };
 
@@ -56,6 +57,7 @@ impl Plugin for CombatPlugin {
                     enter_combat.in_set(CombatState::Initiation),
                 ),
             )
+            .add_systems(OnEnter(HUDState::CombatWall), resolve_combat)
             .add_systems(
                 OnExit(HUDState::CombatWall),
                 exit_combat
@@ -92,7 +94,7 @@ enum CombatState {
 /* -------------------------------------------------------------------------- */
 
 /// The reputation an entity got from one another team
-#[derive(Copy, Clone, PartialEq, Eq, Component)]
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug, Component)]
 pub struct Reputation {
     supreme_god: usize,
     olf: usize,
@@ -125,6 +127,24 @@ impl Reputation {
 #[derive(Component)]
 pub struct Karma(pub i32);
 
+/// Health Points
+#[derive(Copy, Clone, PartialEq, Eq, Reflect, Component)]
+pub struct Hp {
+    pub current: usize,
+    pub max: usize,
+}
+
+impl Hp {
+    pub fn new(max: usize) -> Self {
+        Hp { current: max, max }
+    }
+
+    /// Less than a quarter of the max hp left.
+    pub fn is_badly_hurt(&self) -> bool {
+        self.current * 4 <= self.max
+    }
+}
+
 #[derive(Component)]
 pub struct InCombat;
 
@@ -189,6 +209,8 @@ impl FairPlayTimer {
 /// Happens when:
 ///   - npc::movement::pursue
 ///     - target is reach
+///   - npc::movement::catch_after_invulnerability
+///     - target was reached during their dash
 /// Read in
 ///   - ui::dialog_panel::create_dialog_panel_on_combat_event
 ///     - open combat ui
@@ -263,11 +285,14 @@ pub fn fair_play_wait(
 ///   - Player
 ///     - all companie members (recruted)
 ///   - Foe who caught player
+///
+/// and open the `HUDState::CombatWall`
 pub fn enter_combat(
     mut commands: Commands,
 
     mut ev_combat_enter: EventReader<CombatEvent>,
     mut ev_spawn_fabicurion: EventWriter<SpawnCombatFoesEvent>,
+    mut next_hud_state: ResMut<NextState<HUDState>>,
 
     mut player_query: Query<Entity, (With<Player>, Without<NPC>)>,
     mut player_companie: Query<Entity, (With<NPC>, With<Recruted>)>,
@@ -299,7 +324,33 @@ pub fn enter_combat(
         // display / spawn them in the ui
         // or
         // spawn them in the temple during combat (PREFERED)
+
+        next_hud_state.set(HUDState::CombatWall);
+    }
+}
+
+/// Occurs `OnEnter(HUDState::CombatWall)`
+///
+/// REFACTOR: The fight phases are not implemented (no combat ui):
+/// each member of the player's party strikes every foe once, then the combat ends.
+pub fn resolve_combat(
+    allies_query: Query<
+        (),
+        (
+            Or<(With<Player>, (With<NPC>, With<Recruted>))>,
+            With<InCombat>,
+        ),
+    >,
+    mut foes_query: Query<(&mut Hp, &Name), (With<NPC>, With<InCombat>, Without<Recruted>)>,
+    mut next_hud_state: ResMut<NextState<HUDState>>,
+) {
+    let damage = allies_query.iter().count() * DAMAGE_PER_PARTY_MEMBER;
+    for (mut hp, name) in &mut foes_query {
+        hp.current = hp.current.saturating_sub(damage);
+        info!("{} has {}/{} hp left", name, hp.current, hp.max);
     }
+
+    next_hud_state.set(HUDState::Closed);
 }
 
 /// For each entity in combat, freeze their movement
@@ -339,6 +390,7 @@ pub fn exit_combat(
     >,
 
     foes_query: Query<(Entity, &Name), (With<NPC>, With<InCombat>, Without<Recruted>)>,
+    profile_query: Query<&AIProfile>,
 ) {
     info!("DEBUG: Combat Exit");
 
@@ -351,7 +403,9 @@ pub fn exit_combat(
     for (foes, _name) in foes_query.iter() {
         commands
             .entity(foes)
-            .insert(FairPlayTimer::new(EVASION_TIMER));
+            .insert(FairPlayTimer::new(AIProfile::fair_play_time_of(
+                profile_query.get(foes).ok(),
+            )));
 
         commands.entity(foes).remove::<InCombat>();
     }
@@ -27,6 +27,49 @@ pub mod player {
 
     pub const CAMERA_INTERPOLATION: f32 = 0.1;
 
+    /// Speed multiplier while sneaking.
+    pub const SNEAK_SPEED_FACTOR: f32 = 0.5;
+    /// Speed multiplier while sprinting.
+    pub const SPRINT_SPEED_FACTOR: f32 = 1.6;
+
+    /* -------------------------------------------------------------------------- */
+    /*                                    Dash                                    */
+    /* -------------------------------------------------------------------------- */
+
+    /// Speed multiplier while dashing.
+    pub const DASH_SPEED_FACTOR: f32 = 3.5;
+    pub const DASH_DURATION: f32 = 0.2;
+    /// Cannot be caught, a bit longer than the dash itself.
+    pub const DASH_INVULNERABILITY_DURATION: f32 = 0.35;
+    /// From the start of the dash.
+    pub const DASH_COOLDOWN: f32 = 0.8;
+
+    /* -------------------------------------------------------------------------- */
+    /*                                   Stamina                                  */
+    /* -------------------------------------------------------------------------- */
+
+    pub const MAX_STAMINA: f32 = 100.;
+    pub const SPRINT_STAMINA_PER_S: f32 = 25.;
+    pub const DASH_STAMINA_COST: f32 = 30.;
+    pub const STAMINA_REGEN_PER_S: f32 = 20.;
+    /// Seconds without spending any stamina before it regenerates.
+    pub const STAMINA_REGEN_DELAY: f32 = 0.8;
+
+    /* -------------------------------------------------------------------------- */
+    /*                                Braking Dust                                */
+    /* -------------------------------------------------------------------------- */
+
+    /// Running for one full run loop (4 frames) before stopping raises dust.
+    pub const BRAKING_DUST_RUN_TIME: f32 = 4. * crate::constants::FRAME_TIME;
+    pub const BRAKING_DUST_DURATION: f32 = 0.4;
+    /// Puffs on each side of the feet.
+    pub const BRAKING_DUST_OFFSET: (f32, f32) = (5., super::CHAR_HITBOX_Y_OFFSET);
+    pub const BRAKING_DUST_SIZE: f32 = 3.;
+    /// Size multiplier reached at the end of the dust.
+    pub const BRAKING_DUST_GROWTH: f32 = 2.;
+    pub const BRAKING_DUST_COLOR: bevy::prelude::Color =
+        bevy::prelude::Color::rgba(0.8, 0.75, 0.7, 0.8);
+
     /* -------------------------------------------------------------------------- */
     /*                                  Animation                                 */
     /* -------------------------------------------------------------------------- */
@@ -47,15 +90,17 @@ pub mod player {
     );
 }
 
+pub mod emotes {
+    use crate::constants::{interactions::INTERACT_BUTTON_Z, TILE_SIZE};
+
+    /// Seconds an emote stays above the head.
+    pub const EMOTE_DURATION: f32 = 1.5;
+    pub const EMOTE_FONT_SIZE: f32 = 14.;
+    pub const EMOTE_TRANSLATION: (f32, f32, f32) = (0., 28. * TILE_SIZE, INTERACT_BUTTON_Z);
+}
+
 pub mod npcs {
-    use crate::constants::{
-        interactions::INTERACT_BUTTON_Z,
-        locations::main_room::{
-            landmarks::{LANDMARK_AUDIENCE, LANDMARK_CAT_STATUE},
-            THRONE_POSITION,
-        },
-        TILE_SIZE,
-    };
+    use crate::constants::{interactions::INTERACT_BUTTON_Z, TILE_SIZE};
 
     pub const NPC_SCALE: f32 = super::CHAR_SCALE;
 
@@ -64,12 +109,11 @@ pub mod npcs {
 
     pub const OLF_CAT_SCALE: f32 = 0.5;
     pub const OLF_CAT_POSITION: (f32, f32, f32) = (-104., 134., 0.);
-    pub const SUPREME_GOD_SPAWN_POSITION: (f32, f32, f32) = THRONE_POSITION;
-    pub const OLF_SPAWN_POSITION: (f32, f32, f32) = OLF_CAT_POSITION;
-    pub const VAMPIRE_SPAWN_POSITION: (f32, f32, f32) = LANDMARK_CAT_STATUE[0].0;
-    pub const FABIEN_SPAWN_POSITION: (f32, f32, f32) = LANDMARK_AUDIENCE;
 
     pub const NPC_TALK_INTERACTION_ID: u32 = 10;
+    pub const NPC_MAX_HP: usize = 10;
+    /// Dealt to each foe by each member of the player's party, in a combat.
+    pub const DAMAGE_PER_PARTY_MEMBER: usize = 4;
 
     /* -------------------------------------------------------------------------- */
     /*                                  Animation                                 */
@@ -95,16 +139,162 @@ pub mod npcs {
     pub const OLF_CAT_ANIMATION_DELTA: f32 = 0.5;
     pub const OLF_CAT_HITBOX_OFFSET: (f32, f32, f32) = (0., -5., 0.);
 
+    pub mod cat {
+        use crate::constants::{interactions::INTERACT_BUTTON_Z, TILE_SIZE};
+
+        pub const CAT_PET_INTERACTION_ID: u32 = 11;
+        pub const CAT_INTERACT_BUTTON_POSITION: (f32, f32, f32) =
+            (10. * TILE_SIZE, 10. * TILE_SIZE, INTERACT_BUTTON_Z);
+
+        /// Speed multipliers, from the cat's `Speed`.
+        pub const CAT_WALK_FACTOR: f32 = 0.4;
+        pub const CAT_FOLLOW_FACTOR: f32 = 1.2;
+        pub const CAT_FLEE_FACTOR: f32 = 2.;
+
+        /// Close enough to a spot to stop there.
+        pub const CAT_ARRIVAL_DISTANCE: f32 = 3. * TILE_SIZE;
+        /// Wanders to a random spot in this radius.
+        pub const CAT_WANDER_RADIUS: f32 = 40. * TILE_SIZE;
+        /// Stays this close to the player who petted it.
+        pub const CAT_FOLLOW_DISTANCE: f32 = 15. * TILE_SIZE;
+
+        /// Anything moving faster than this, in this radius, scares the cat.
+        /// (anyone walking, `NPC_SPEED`, is slow enough)
+        pub const CAT_STARTLE_RADIUS: f32 = 30. * TILE_SIZE;
+        pub const CAT_STARTLE_SPEED: f32 = 60. * TILE_SIZE;
+        /// Stops fleeing this far from the threat.
+        pub const CAT_SAFE_DISTANCE: f32 = 70. * TILE_SIZE;
+
+        /// Seconds spent in each state (once arrived, except to wander and follow).
+        pub const CAT_WANDER_DURATION: f32 = 6.;
+        pub const CAT_NAP_DURATION: f32 = 20.;
+        pub const CAT_THRONE_DURATION: f32 = 12.;
+        pub const CAT_FOLLOW_DURATION: f32 = 30.;
+        /// Gives up fleeing after this long, even if the threat is still close.
+        pub const CAT_FLEE_DURATION: f32 = 5.;
+
+        /// Chances to pick a nap or the throne as next activity (otherwise wanders).
+        pub const CAT_NAP_CHANCE: f64 = 0.35;
+        pub const CAT_THRONE_CHANCE: f64 = 0.15;
+    }
+
     pub mod movement {
         use crate::constants::TILE_SIZE;
 
         pub const REST_TIMER: u64 = 3;
+        /// Rest at a landmark of a `LandmarkGroup`, long enough for a conversation to start.
+        pub const GATHERING_REST_TIMER: u64 = 10;
         // TODO: adjust EVASION_TIMER / FAIR_PLAY_TIMER
         pub const EVASION_TIMER: u64 = 5;
+        /// Seconds before an unclaimed landmark reservation is canceled.
+        pub const RESERVATION_TIMEOUT: u64 = 30;
+
+        /// Neutral npcs in this radius will flee from a starting combat.
+        pub const FLEE_ALARM_RADIUS: f32 = 80. * TILE_SIZE;
+        /// Seconds before a fleer gives up, even if the threat is still close.
+        pub const FLEE_DURATION: f32 = 10.;
+
+        /// Characters closer than this will push each other away.
+        pub const AVOIDANCE_RADIUS: f32 = 12. * TILE_SIZE;
+        pub const SEPARATION_WEIGHT: f32 = 1.5;
 
         pub const NPC_SPEED_LEADER: f32 = 70. * TILE_SIZE;
         pub const NPC_SPEED: f32 = 50. * TILE_SIZE; // -> Speed::default()
     }
+
+    pub mod perception {
+        use crate::constants::TILE_SIZE;
+
+        pub const VISION_RANGE: f32 = 60. * TILE_SIZE;
+        pub const VISION_HALF_ANGLE: f32 = std::f32::consts::FRAC_PI_4;
+        pub const HEARING_RADIUS_RUN: f32 = 35. * TILE_SIZE;
+        pub const HEARING_RADIUS_IDLE: f32 = 10. * TILE_SIZE;
+    }
+
+    pub mod alert {
+        use crate::constants::TILE_SIZE;
+
+        /// Allies in this radius are warned by a npc starting a chase.
+        pub const ALERT_RADIUS: f32 = 120. * TILE_SIZE;
+        /// Warned `TargetSeeker`s in this radius directly join the chase,
+        /// the others investigate.
+        pub const ALERT_JOIN_RADIUS: f32 = 50. * TILE_SIZE;
+        /// Alert level lost per second (a fresh alert is 1.)
+        pub const ALERT_DECAY_RATE: f32 = 0.05;
+    }
+
+    pub mod investigation {
+        use crate::constants::TILE_SIZE;
+
+        /// Close enough to the last known position to look around.
+        pub const INVESTIGATION_DISTANCE: f32 = 5. * TILE_SIZE;
+        /// Seconds before giving up on reaching the last known position (e.g. blocked).
+        pub const INVESTIGATION_WALK_DURATION: f32 = 8.;
+        /// Seconds spent looking around the last known position.
+        pub const LOOK_AROUND_DURATION: f32 = 4.;
+        pub const LOOK_AROUND_TURN_DELAY: f32 = 1.;
+    }
+
+    pub mod memory {
+        use crate::constants::TILE_SIZE;
+
+        /// Seconds before a memory is forgotten.
+        pub const TALKED_TO_MEMORY_DURATION: f32 = 120.;
+        pub const ATTACKED_MEMORY_DURATION: f32 = 600.;
+        pub const SAW_SECRET_BANNER_MEMORY_DURATION: f32 = 900.;
+        pub const RECRUITED_MEMORY_DURATION: f32 = 600.;
+        pub const DISMISSED_MEMORY_DURATION: f32 = 300.;
+
+        /// Npcs in this radius around the player see the secret banner being opened.
+        pub const SECRET_BANNER_WITNESS_RADIUS: f32 = 80. * TILE_SIZE;
+        /// Suspicion rate multiplier against a target remembered as an attacker.
+        pub const ATTACKER_SUSPICION_FACTOR: f32 = 2.;
+    }
+
+    pub mod roster {
+        /// See `characters::npcs::roster::NPCRoster`.
+        pub const NPC_ROSTER_FILE: &str = "data/npcs.ron";
+    }
+
+    pub mod profile {
+        /// See `characters::npcs::profile::AIProfiles`.
+        pub const AI_PROFILES_FILE: &str = "data/ai_profiles.ron";
+        /// Given to the npcs without profile in the roster.
+        pub const DEFAULT_AI_PROFILE: &str = "villager";
+
+        // -- Default profile, in tiles --
+        pub const INTERACTION_SENSOR_RADIUS: f32 = 15.;
+        pub const PURSUIT_SENSOR_RADIUS: f32 = 60.;
+        pub const DETECTION_SENSOR_RADIUS: f32 = 40.;
+        pub const FOLLOW_SENSOR_RADIUS: f32 = 20.;
+        pub const CLOSE_SENSOR_RADIUS: f32 = 10.;
+    }
+
+    pub mod suspicion {
+        use crate::constants::{interactions::INTERACT_BUTTON_Z, TILE_SIZE};
+
+        /// Suspicion gained per second while seeing the target (full suspicion is 1.)
+        pub const SUSPICION_SIGHT_RATE: f32 = 0.6;
+        /// Suspicion gained per second while hearing the target.
+        pub const SUSPICION_HEARING_RATE: f32 = 1.2;
+        /// Suspicion lost per second while not perceiving any target.
+        pub const SUSPICION_DECAY_RATE: f32 = 0.2;
+
+        pub const SUSPICION_METER_SIZE: (f32, f32) = (16. * TILE_SIZE, 2. * TILE_SIZE);
+        pub const SUSPICION_METER_TRANSLATION: (f32, f32, f32) =
+            (0., 22. * TILE_SIZE, INTERACT_BUTTON_Z);
+    }
+}
+
+pub mod conversation {
+    use crate::constants::{interactions::INTERACT_BUTTON_Z, TILE_SIZE};
+
+    pub const NPC_CONVERSATIONS_PATH: &str = "data/npc_conversations.yml";
+
+    /// Seconds each line stays above the speaker's head.
+    pub const CONVERSATION_LINE_DELAY: f32 = 2.5;
+    pub const SPEECH_BUBBLE_TRANSLATION: (f32, f32, f32) = (0., 30. * TILE_SIZE, INTERACT_BUTTON_Z);
+    pub const SPEECH_BUBBLE_FONT_SIZE: f32 = 10.;
 }
 
 pub mod dialog {
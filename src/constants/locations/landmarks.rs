@@ -0,0 +1,12 @@
+pub const LANDMARK_SENSOR_SIZE: f32 = 2.5;
+
+/// One RON file per location, see `locations::landmarks::LandmarkFile`.
+pub const LANDMARKS_DIRECTORY: &str = "data/landmarks";
+pub const HALL_LANDMARKS_FILE: &str = "hall.ron";
+pub const TEMPLE_LANDMARKS_FILE: &str = "temple.ron";
+pub const SECRET_ROOM_LANDMARKS_FILE: &str = "secret_room.ron";
+
+/// Seconds between two checks for modified landmark files.
+pub const LANDMARKS_RELOAD_DELAY: f32 = 1.;
+/// Distance between two spots of the same landmark, when its capacity is more than one.
+pub const LANDMARK_SPOT_SPACING: f32 = 4. * LANDMARK_SENSOR_SIZE;
@@ -1,5 +1,3 @@
-pub mod landmarks;
-
 use crate::constants::{interactions::INTERACT_BUTTON_Z, TILE_SIZE};
 
 use super::{MAP_DISTANCE_IN_Z, MAP_START_Y, PROPS_Z, ROOF_Z, WILL_BE_COMPUTE_LATER, Y_UNIT};
@@ -1,4 +1,5 @@
 pub mod hall;
+pub mod landmarks;
 pub mod main_room;
 pub mod secret_room;
 
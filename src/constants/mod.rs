@@ -21,6 +21,28 @@ pub const TILE_SIZE: f32 = 1.;
 
 pub const FRAME_TIME: f32 = 0.1;
 
+pub mod controls {
+    /// Sub-directory of the platform config directory.
+    pub const CONFIG_DIRECTORY: &str = "fabien-et-la-trahison-de-olf";
+    pub const KEY_BINDINGS_FILE: &str = "key_bindings.ron";
+    pub const CORRUPT_KEY_BINDINGS_EXTENSION: &str = "ron.corrupt";
+
+    /// Radial dead zone of the sticks, from 0 to 1.
+    pub const GAMEPAD_DEAD_ZONE: f32 = 0.2;
+}
+
+pub mod save {
+    /// Incremented, with a new migration, at each change of the save format.
+    pub const SAVE_VERSION: u32 = 1;
+    /// In the directory of the game, in the platform data directory.
+    pub const SAVES_DIRECTORY: &str = "saves";
+    pub const QUICK_SAVE_SLOT: usize = 0;
+    /// Used in turn, the oldest one first.
+    pub const AUTOSAVE_SLOTS: [usize; 3] = [1, 2, 3];
+    /// Doors restored by a load open or close faster.
+    pub const RESTORED_DOOR_DELTA_S: f32 = 0.02;
+}
+
 pub mod interactions {
     pub const INTERACT_BUTTON_Z: f32 = 20.;
     pub const INTERACT_BUTTON_SCALE: f32 = 0.25;
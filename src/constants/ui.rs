@@ -18,3 +18,50 @@ pub mod dialogs {
     pub const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
     pub const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
 }
+
+pub mod options {
+    use bevy::prelude::{Color, KeyCode};
+
+    /// Opens and closes the options panel.
+    pub const OPTIONS_KEY: KeyCode = KeyCode::F1;
+
+    pub const OPTIONS_FONT_SIZE: f32 = 30.;
+    pub const OPTIONS_TITLE_FONT_SIZE: f32 = 50.;
+    pub const OPTIONS_ROW_GAP: f32 = 20.;
+    pub const OPTIONS_ACTION_WIDTH: f32 = 200.;
+    pub const OPTIONS_KEY_WIDTH: f32 = 160.;
+    pub const OPTIONS_ADD_KEY_WIDTH: f32 = 40.;
+
+    pub const OPTIONS_BACKGROUND: Color = Color::rgba(0.1, 0.05, 0.07, 0.9);
+    pub const OPTIONS_STATUS_COLOR: Color = Color::rgb(0.8, 0.8, 0.8);
+    pub const OPTIONS_NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+    pub const OPTIONS_HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+    pub const OPTIONS_WAITING_BUTTON: Color = Color::rgb(0.75, 0.55, 0.15);
+
+    /// Added or removed by the volume buttons.
+    pub const OPTIONS_VOLUME_STEP: f32 = 0.05;
+}
+
+pub mod stamina {
+    use bevy::prelude::Color;
+
+    pub const STAMINA_BAR_SIZE: (f32, f32) = (200., 12.);
+    /// From the bottom left corner of the screen.
+    pub const STAMINA_BAR_MARGIN: f32 = 20.;
+    pub const STAMINA_BAR_BACKGROUND: Color = Color::rgba(0., 0., 0., 0.6);
+    pub const STAMINA_BAR_FILL: Color = Color::rgb(0.35, 0.75, 0.35);
+    /// Not enough stamina left to dash.
+    pub const STAMINA_BAR_LOW_FILL: Color = Color::rgb(0.75, 0.35, 0.35);
+}
+
+pub mod pause {
+    use bevy::prelude::{Color, KeyCode};
+
+    /// Pauses and resumes the game; also closes the options.
+    pub const PAUSE_KEY: KeyCode = KeyCode::Escape;
+
+    pub const PAUSE_BUTTON_WIDTH: f32 = 260.;
+    pub const PAUSE_BACKGROUND: Color = Color::rgba(0., 0., 0., 0.6);
+    /// The music volume is multiplied by it while paused.
+    pub const PAUSED_MUSIC_DUCKING: f32 = 0.3;
+}
@@ -1,13 +1,91 @@
-use bevy::prelude::*;
+//! Key bindings
+//!
+//! The `KeyBindings` are kept in `key_bindings.ron`, in the config directory of the platform
+//! (`~/.config/fabien-et-la-trahison-de-olf/` on Linux).
+//! A missing file gives the default bindings; a corrupt one is set aside and replaced by them.
+//!
+//! Systems read the input through `ActionInput`, never through the keys directly:
+//! it merges the keyboard and the gamepads (`GamepadBindings`, left stick to move).
+
+use bevy::{ecs::system::SystemParam, prelude::*};
 use serde::{
     de::{self, Visitor},
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use std::fmt;
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter};
+
+use crate::constants::controls::*;
+
+pub struct ControlsPlugin;
 
-#[derive(Component, Debug, Deref, DerefMut, Clone, Copy)]
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_key_bindings())
+            .init_resource::<GamepadBindings>()
+            .add_systems(
+                Update,
+                save_key_bindings.run_if(resource_changed::<KeyBindings>()),
+            );
+    }
+}
+
+#[derive(Component, Debug, Deref, DerefMut, Clone, Copy, PartialEq, Eq)]
 pub struct Key(pub KeyCode);
 
+/// What the player can bind keys to.
+#[derive(
+    Serialize,
+    Deserialize,
+    Display,
+    EnumIter,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Interact,
+    Sneak,
+    /// Faster, while some stamina is left.
+    Sprint,
+    /// A short burst of speed, during which the player cannot be caught.
+    Dash,
+    /// Opens the dialog panel with the player's thoughts.
+    ToggleHUD,
+    /// Direct choice of the first answer of a dialog.
+    Choice1,
+    Choice2,
+    Choice3,
+    QuickSave,
+    QuickLoad,
+}
+
+impl Action {
+    /// The answer directly chosen by this action, if any.
+    pub fn choice_position(&self) -> Option<usize> {
+        match self {
+            Action::Choice1 => Some(0),
+            Action::Choice2 => Some(1),
+            Action::Choice3 => Some(2),
+            _ => None,
+        }
+    }
+}
+
 impl Serialize for Key {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         serializer.serialize_u32(**self as u32)
@@ -27,7 +105,7 @@ impl<'de> Deserialize<'de> for Key {
 
             fn visit_u32<E: de::Error>(self, v: u32) -> Result<Self::Value, E> {
                 Ok(Key(match v {
-                    0 => KeyCode::Key2,
+                    0 => KeyCode::Key1,
                     1 => KeyCode::Key2,
                     2 => KeyCode::Key3,
                     3 => KeyCode::Key4,
@@ -190,7 +268,7 @@ impl<'de> Deserialize<'de> for Key {
                     160 => KeyCode::Copy,
                     161 => KeyCode::Paste,
                     162 => KeyCode::Cut,
-                    v => unreachable!("Deserialized unknown KeyCode {v}"),
+                    v => return Err(E::custom(format!("unknown KeyCode {v}"))),
                 }))
             }
         }
@@ -199,39 +277,254 @@ impl<'de> Deserialize<'de> for Key {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Resource)]
-pub struct KeyBindings {
-    pub up: [Key; 3],
-    pub down: [Key; 2],
-    pub left: [Key; 3],
-    pub right: [Key; 2],
-    pub interact: [Key; 2],
-}
+/// Any number of keys per action.
+#[derive(Serialize, Deserialize, Clone, Debug, Deref, DerefMut, Resource)]
+pub struct KeyBindings(pub BTreeMap<Action, Vec<Key>>);
 
 impl KeyBindings {
-    pub fn up(&self) -> [KeyCode; 2] {
-        [*self.up[0], *self.up[1]]
+    pub fn keys(&self, action: Action) -> &[Key] {
+        self.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn keys_mut(&mut self, action: Action) -> &mut Vec<Key> {
+        self.entry(action).or_default()
+    }
+
+    pub fn key_codes(&self, action: Action) -> impl Iterator<Item = KeyCode> + '_ {
+        self.keys(action).iter().map(|key| key.0)
+    }
+
+    /// The action, and the slot, bound to this key.
+    pub fn bound_to(&self, key_code: KeyCode) -> Option<(Action, usize)> {
+        Action::iter().find_map(|action| {
+            self.keys(action)
+                .iter()
+                .position(|key| **key == key_code)
+                .map(|index| (action, index))
+        })
+    }
+}
+
+impl Default for KeyBindings {
+    /// Both AZERTY (ZQSD) and QWERTY (WASD) layouts, and the arrows.
+    fn default() -> Self {
+        KeyBindings(BTreeMap::from([
+            (
+                Action::Up,
+                vec![Key(KeyCode::W), Key(KeyCode::Z), Key(KeyCode::Up)],
+            ),
+            (Action::Down, vec![Key(KeyCode::S), Key(KeyCode::Down)]),
+            (
+                Action::Left,
+                vec![Key(KeyCode::A), Key(KeyCode::Q), Key(KeyCode::Left)],
+            ),
+            (Action::Right, vec![Key(KeyCode::D), Key(KeyCode::Right)]),
+            (Action::Interact, vec![Key(KeyCode::E), Key(KeyCode::R)]),
+            (
+                Action::Sneak,
+                vec![Key(KeyCode::ControlLeft), Key(KeyCode::C)],
+            ),
+            (Action::Sprint, vec![Key(KeyCode::ShiftLeft)]),
+            (Action::Dash, vec![Key(KeyCode::Space)]),
+            (Action::ToggleHUD, vec![Key(KeyCode::O)]),
+            (
+                Action::Choice1,
+                vec![Key(KeyCode::Key1), Key(KeyCode::Numpad1)],
+            ),
+            (
+                Action::Choice2,
+                vec![Key(KeyCode::Key2), Key(KeyCode::Numpad2)],
+            ),
+            (
+                Action::Choice3,
+                vec![Key(KeyCode::Key3), Key(KeyCode::Numpad3)],
+            ),
+            (Action::QuickSave, vec![Key(KeyCode::F5)]),
+            (Action::QuickLoad, vec![Key(KeyCode::F9)]),
+        ]))
+    }
+}
+
+/// Buttons of any connected gamepad; not saved.
+#[derive(Clone, Debug, Deref, DerefMut, Resource)]
+pub struct GamepadBindings(pub BTreeMap<Action, Vec<GamepadButtonType>>);
+
+impl GamepadBindings {
+    pub fn buttons(&self, action: Action) -> &[GamepadButtonType] {
+        self.get(&action).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl Default for GamepadBindings {
+    fn default() -> Self {
+        GamepadBindings(BTreeMap::from([
+            (Action::Up, vec![GamepadButtonType::DPadUp]),
+            (Action::Down, vec![GamepadButtonType::DPadDown]),
+            (Action::Left, vec![GamepadButtonType::DPadLeft]),
+            (Action::Right, vec![GamepadButtonType::DPadRight]),
+            (Action::Interact, vec![GamepadButtonType::South]),
+            (Action::Sneak, vec![GamepadButtonType::LeftTrigger]),
+            (Action::Sprint, vec![GamepadButtonType::RightTrigger]),
+            (Action::Dash, vec![GamepadButtonType::East]),
+            (Action::ToggleHUD, vec![GamepadButtonType::Select]),
+        ]))
+    }
+}
+
+/// The state of the actions, whatever the key or the gamepad button bound.
+///
+/// ```ignore
+/// fn system(action_input: ActionInput) {
+///     if action_input.just_pressed(Action::Interact) { ... }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    key_bindings: Res<'w, KeyBindings>,
+    keyboard_input: Res<'w, Input<KeyCode>>,
+    gamepad_bindings: Res<'w, GamepadBindings>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_input: Res<'w, Input<GamepadButton>>,
+    gamepad_axes: Res<'w, Axis<GamepadAxis>>,
+}
+
+impl ActionInput<'_> {
+    /// The buttons bound to the action, on each gamepad connected.
+    fn gamepad_buttons(&self, action: Action) -> impl Iterator<Item = GamepadButton> + '_ {
+        self.gamepads.iter().flat_map(move |gamepad| {
+            self.gamepad_bindings
+                .buttons(action)
+                .iter()
+                .map(move |button_type| GamepadButton::new(gamepad, *button_type))
+        })
     }
 
-    pub fn down(&self) -> [KeyCode; 2] {
-        [*self.down[0], *self.down[1]]
+    pub fn pressed(&self, action: Action) -> bool {
+        self.keyboard_input
+            .any_pressed(self.key_bindings.key_codes(action))
+            || self.gamepad_input.any_pressed(self.gamepad_buttons(action))
     }
 
-    pub fn left(&self) -> [KeyCode; 2] {
-        [*self.left[0], *self.left[1]]
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.keyboard_input
+            .any_just_pressed(self.key_bindings.key_codes(action))
+            || self
+                .gamepad_input
+                .any_just_pressed(self.gamepad_buttons(action))
     }
 
-    pub fn right(&self) -> [KeyCode; 2] {
-        [*self.right[0], *self.right[1]]
+    pub fn just_released(&self, action: Action) -> bool {
+        self.keyboard_input
+            .any_just_released(self.key_bindings.key_codes(action))
+            || self
+                .gamepad_input
+                .any_just_released(self.gamepad_buttons(action))
     }
 
-    pub fn interact(&self) -> [KeyCode; 2] {
-        [*self.interact[0], *self.interact[1]]
+    /// The first left stick out of its dead zone, rescaled from 0 to 1.
+    fn left_stick(&self) -> Option<Vec2> {
+        self.gamepads.iter().find_map(|gamepad| {
+            let x = self
+                .gamepad_axes
+                .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))?;
+            let y = self
+                .gamepad_axes
+                .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))?;
+            let stick = Vec2::new(x, y);
+            let length = stick.length();
+            if length <= GAMEPAD_DEAD_ZONE {
+                return None;
+            }
+            let scaled_length = ((length - GAMEPAD_DEAD_ZONE) / (1. - GAMEPAD_DEAD_ZONE)).min(1.);
+            Some(stick / length * scaled_length)
+        })
     }
+
+    /// Wished direction, of length 1 at most:
+    /// the analog stick if pushed, the (normalized) directional actions otherwise.
+    pub fn movement(&self) -> Vec2 {
+        if let Some(stick) = self.left_stick() {
+            return stick;
+        }
+        let x_axis = -(self.pressed(Action::Left) as i8) + self.pressed(Action::Right) as i8;
+        let y_axis = -(self.pressed(Action::Down) as i8) + self.pressed(Action::Up) as i8;
+        Vec2::new(x_axis as f32, y_axis as f32).normalize_or_zero()
+    }
+}
+
+#[derive(Debug)]
+pub enum KeyBindingsError {
+    Io(std::io::Error),
+    Parse(ron::Error),
+    Serialize(ron::Error),
 }
 
-pub fn save_key_bindings(_key_bindings: Res<KeyBindings>) {}
+/// `None` if the platform has no config directory (wasm).
+fn key_bindings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|config_dir| config_dir.join(CONFIG_DIRECTORY).join(KEY_BINDINGS_FILE))
+}
+
+fn read_key_bindings_file(path: &Path) -> Result<KeyBindings, KeyBindingsError> {
+    let content = fs::read_to_string(path).map_err(KeyBindingsError::Io)?;
+    ron::from_str(&content).map_err(KeyBindingsError::Parse)
+}
 
-// pub fn load_key_bindings() -> KeyBindings {
-//     ron::de::from_bytes(include_bytes!("data/dialogs.ron")).unwrap()
-// }
+fn write_key_bindings_file(
+    path: &Path,
+    key_bindings: &KeyBindings,
+) -> Result<(), KeyBindingsError> {
+    let content = ron::ser::to_string_pretty(key_bindings, ron::ser::PrettyConfig::default())
+        .map_err(KeyBindingsError::Serialize)?;
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(KeyBindingsError::Io)?;
+    }
+    fs::write(path, content).map_err(KeyBindingsError::Io)
+}
+
+/// The saved bindings, or the default ones.
+///
+/// A corrupt file is renamed `key_bindings.ron.corrupt`,
+/// to not be overwritten at the next save.
+pub fn load_key_bindings() -> KeyBindings {
+    let Some(path) = key_bindings_path() else {
+        warn!("No config directory: default key bindings");
+        return KeyBindings::default();
+    };
+
+    match read_key_bindings_file(&path) {
+        Ok(mut key_bindings) => {
+            info!("Key bindings loaded from {}", path.display());
+            // Actions added since the last save get their default keys
+            for (action, keys) in KeyBindings::default().0 {
+                key_bindings.entry(action).or_insert(keys);
+            }
+            key_bindings
+        }
+        Err(KeyBindingsError::Io(error)) if error.kind() == std::io::ErrorKind::NotFound => {
+            info!("No key bindings saved yet: default ones");
+            KeyBindings::default()
+        }
+        Err(error) => {
+            error!("Invalid key bindings {}: {error:?}", path.display());
+            let backup = path.with_extension(CORRUPT_KEY_BINDINGS_EXTENSION);
+            if let Err(rename_error) = fs::rename(&path, &backup) {
+                error!("Could not set aside {}: {rename_error:?}", path.display());
+            }
+            KeyBindings::default()
+        }
+    }
+}
+
+/// Runs each time the `KeyBindings` change (and at startup, which creates the file).
+pub fn save_key_bindings(key_bindings: Res<KeyBindings>) {
+    let Some(path) = key_bindings_path() else {
+        return;
+    };
+    match write_key_bindings_file(&path, &key_bindings) {
+        Ok(()) => info!("Key bindings saved in {}", path.display()),
+        Err(error) => error!(
+            "Could not save key bindings in {}: {error:?}",
+            path.display()
+        ),
+    }
+}
@@ -1,14 +1,31 @@
 use bevy::prelude::*;
-use bevy_inspector_egui::quick::{StateInspectorPlugin, WorldInspectorPlugin};
+use bevy_inspector_egui::quick::{
+    ResourceInspectorPlugin, StateInspectorPlugin, WorldInspectorPlugin,
+};
 
 use crate::{
     animations::sprite_sheet_animation::{
         AnimationIndices, CharacterState, SpriteSheetAnimation, TempoAnimation,
     },
-    characters::npcs::movement::{Chaser, NPCBehavior, TargetSeeker, TargetType},
+    characters::{
+        emotes::Emote,
+        npcs::{
+            alert::Alert,
+            cat::{CatBehavior, CatState},
+            flee::Fleer,
+            investigation::Investigation,
+            memory::{Memory, MemoryKind, Recollection},
+            movement::{Chaser, NPCBehavior, TargetSeeker, TargetType},
+            perception::Perception,
+            profile::{AIProfile, AIProfiles},
+            suspicion::Suspicion,
+        },
+        stamina::Stamina,
+    },
     collisions::{TesselatedCollider, TesselatedColliderConfig},
+    combat::Hp,
     locations::{
-        landmarks::Landmark,
+        landmarks::{Landmark, LandmarkStatus, LandmarkTag},
         temple::{Location, OverlappingEntity},
     },
     menu::{ManorLightsPattern, ManorLightsTimer},
@@ -39,16 +56,34 @@ impl Plugin for DebugPlugin {
                 /* -------------------------------------------------------------------------- */
                 .register_type::<AnimationIndices>()
                 .register_type::<CharacterState>()
+                .register_type::<Emote>()
                 .register_type::<NPCBehavior>()
                 .register_type::<TargetSeeker>()
                 .register_type::<TargetType>()
                 .register_type::<Chaser>()
+                .register_type::<Fleer>()
+                .register_type::<Alert>()
+                .register_type::<Investigation>()
+                .register_type::<Memory>()
+                .register_type::<MemoryKind>()
+                .register_type::<Recollection>()
+                .register_type::<AIProfile>()
+                .register_type::<AIProfiles>()
+                .add_plugins((ResourceInspectorPlugin::<AIProfiles>::default(),))
+                .register_type::<CatBehavior>()
+                .register_type::<CatState>()
+                .register_type::<Perception>()
+                .register_type::<Suspicion>()
+                .register_type::<Hp>()
+                .register_type::<Stamina>()
                 .register_type::<Location>()
                 /* -------------------------------------------------------------------------- */
                 /*                                     Map                                    */
                 /* -------------------------------------------------------------------------- */
                 .register_type::<OverlappingEntity>()
                 .register_type::<Landmark>()
+                .register_type::<LandmarkStatus>()
+                .register_type::<LandmarkTag>()
                 /* -------------------------------------------------------------------------- */
                 /*                                   Hitbox                                   */
                 /* -------------------------------------------------------------------------- */
@@ -1,19 +1,27 @@
 use crate::collisions::CollisionEventExt;
 use crate::{
-    characters::{npcs::CharacterInteractionEvent, player::PlayerInteractionSensor},
+    characters::{
+        npcs::{cat::PetCatEvent, CharacterInteractionEvent},
+        player::PlayerInteractionSensor,
+    },
     constants::{
-        character::npcs::{CHARACTER_INTERACT_BUTTON_POSITION, NPC_TALK_INTERACTION_ID},
+        character::npcs::{
+            cat::CAT_PET_INTERACTION_ID, CHARACTER_INTERACT_BUTTON_POSITION,
+            NPC_TALK_INTERACTION_ID,
+        },
         locations::{
             hall::{BOX_INTERACTION_ID, DOOR_INTERACTION_ID, DOOR_OPEN_DELTA_S},
             main_room::{BANNER_INTERACTION_ID, BANNER_OPEN_DELTA_S},
         },
     },
-    controls::KeyBindings,
+    controls::{Action, ActionInput},
+    hud_closed,
     locations::temple::{
         hall::{PropsInteractionEvent, TempleDoor},
         main_room::{SecretBanner, SecretBannerEvent},
         DoorInteractEvent, DoorState,
     },
+    paused,
 };
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::CollisionEvent;
@@ -26,7 +34,11 @@ impl Plugin for InteractionsPlugin {
             .add_systems(Startup, setup_interactions)
             .add_systems(
                 Update,
-                (interaction_icon_events, interaction_icon, interaction),
+                (
+                    interaction_icon_events,
+                    interaction_icon,
+                    interaction.run_if(hud_closed).run_if(not(paused)),
+                ),
             );
     }
 }
@@ -144,8 +156,7 @@ pub fn interaction_icon(
 
 /// TODO: Only interact with the closest interactible
 pub fn interaction(
-    key_bindings: Res<KeyBindings>,
-    keyboard_input: Res<Input<KeyCode>>,
+    action_input: ActionInput,
     interactibles_query: Query<(Entity, &Interactible)>,
 
     temple_door_query: Query<Entity, With<TempleDoor>>,
@@ -155,8 +166,9 @@ pub fn interaction(
     mut secret_banner_event: EventWriter<SecretBannerEvent>,
     mut props_interaction_event: EventWriter<PropsInteractionEvent>,
     mut character_interact_event: EventWriter<CharacterInteractionEvent>,
+    mut pet_cat_event: EventWriter<PetCatEvent>,
 ) {
-    if keyboard_input.any_just_pressed(key_bindings.interact()) {
+    if action_input.just_pressed(Action::Interact) {
         for (entity, interactible) in interactibles_query.iter() {
             if interactible.in_range {
                 match interactible.interaction_id {
@@ -181,6 +193,9 @@ pub fn interaction(
                     NPC_TALK_INTERACTION_ID => {
                         character_interact_event.send(CharacterInteractionEvent(entity));
                     }
+                    CAT_PET_INTERACTION_ID => {
+                        pet_cat_event.send(PetCatEvent(entity));
+                    }
                     id => error!("Unknown interaction id {id}"),
                 }
             }
@@ -3,26 +3,35 @@
 //!
 //! For example, it could be the throne side, statue admiration, etc.
 
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 use bevy_rapier2d::prelude::{ActiveEvents, Collider, CollisionEvent, Sensor};
 use rand::seq::IteratorRandom;
-use std::time::Duration;
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
 
 use crate::{
     characters::{
-        npcs::{idle::RestTime, movement::NPCBehavior, NPC},
+        npcs::{
+            idle::RestTime, movement::NPCBehavior, profile::AIProfile, roster::LandmarkSchedule,
+            NPC,
+        },
         player::Player,
         CharacterHitbox,
     },
     collisions::CollisionEventExt,
     constants::{
-        character::npcs::movement::REST_TIMER,
-        locations::{
-            hall,
-            main_room::{self, landmarks::LANDMARK_SENSOR_SIZE},
-            secret_room,
+        character::npcs::movement::RESERVATION_TIMEOUT,
+        locations::landmarks::{
+            HALL_LANDMARKS_FILE, LANDMARKS_DIRECTORY, LANDMARKS_RELOAD_DELAY, LANDMARK_SENSOR_SIZE,
+            LANDMARK_SPOT_SPACING, SECRET_ROOM_LANDMARKS_FILE, TEMPLE_LANDMARKS_FILE,
         },
+        TILE_SIZE,
     },
+    GameState,
 };
 
 use super::temple::Location;
@@ -31,28 +40,53 @@ pub struct LandmarkPlugin;
 
 impl Plugin for LandmarkPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            // OnEnter(GameState::Playing),
-            Startup,
-            spawn_landmarks,
-        )
-        .add_systems(Update, landmark_arrival);
+        app.init_resource::<LandmarkFilesWatcher>()
+            .add_systems(
+                // OnEnter(GameState::Playing),
+                Startup,
+                spawn_landmarks,
+            )
+            .add_systems(
+                Update,
+                (
+                    landmark_arrival,
+                    reservation_timeout,
+                    release_landmarks_on_despawn,
+                    hot_reload_landmarks.run_if(in_state(GameState::Playing)),
+                ),
+            )
+            .add_systems(OnExit(GameState::Playing), free_all_landmarks);
     }
 }
 
 /// Npcs will try with the `NPCBehavior::LandmarkSeeking`
 /// to occupy one of the free landmark.
 /// The npc will reserved the landmark, only the player can occupy a reserved landmark.
-/// A occupied landmark can only be freed by the occupant.
+/// A occupied landmark can only be freed by the occupant,
+/// or taken over by the player.
 #[derive(PartialEq, Eq, Debug, Reflect, Default, Component)]
 pub enum LandmarkStatus {
     #[default]
     Free,
-    Reserved,
+    ReservedBy(Entity),
     OccupiedBy(Entity),
 }
 
-#[derive(Clone, Copy, Reflect, Debug, Component)]
+/// Used to reserve a specific kind of landmark.
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Reflect, Debug)]
+pub enum LandmarkTag {
+    /// Part of a discussion group
+    Gathering,
+    Pillar,
+    FlowerPanel,
+    /// The player is `Hidden` while occupying it
+    Hide,
+    /// A favourite spot of the Olf Cat, never given to npcs
+    CatNap,
+    Throne,
+}
+
+#[derive(Deserialize, Clone, Copy, Reflect, Debug, Component)]
 pub enum Direction {
     Right,
     Left,
@@ -73,6 +107,12 @@ pub struct Landmark {
     pub location: Location,
     /// Used for `flip_x`.
     pub direction: Option<Direction>,
+    pub name: String,
+    /// Name of the `LandmarkGroup`, if any.
+    pub group: Option<String>,
+    pub tags: Vec<LandmarkTag>,
+    /// A reservation never claimed is canceled when this timer is finished.
+    pub reservation_timer: Timer,
 }
 
 impl Landmark {
@@ -81,6 +121,109 @@ impl Landmark {
             status: LandmarkStatus::default(),
             location: landmark_location,
             direction,
+            name: String::new(),
+            group: None,
+            tags: Vec::new(),
+            reservation_timer: Timer::new(
+                Duration::from_secs(RESERVATION_TIMEOUT),
+                TimerMode::Once,
+            ),
+        }
+    }
+}
+
+/// Content of a landmark file: all landmarks of a location.
+#[derive(Deserialize, Debug, Default)]
+pub struct LandmarkFile {
+    pub landmarks: Vec<LandmarkDefinition>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LandmarkDefinition {
+    pub name: String,
+    /// In tiles.
+    pub position: (f32, f32),
+    pub direction: Option<Direction>,
+    /// Landmarks of the same group form a `LandmarkGroup`.
+    pub group: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<LandmarkTag>,
+    /// Number of spots, side by side, around the position.
+    pub capacity: usize,
+}
+
+impl LandmarkDefinition {
+    /// One `Landmark` (and its translation) per spot.
+    fn landmarks(&self, location: Location) -> Vec<(Landmark, Vec3)> {
+        let name = match &self.group {
+            Some(group_name) => format!("{group_name} {}", self.name),
+            None => self.name.clone(),
+        };
+        let (x, y) = self.position;
+        let center = Vec3::new(x * TILE_SIZE, y * TILE_SIZE, 0.);
+
+        (0..self.capacity)
+            .map(|spot| {
+                let landmark = Landmark {
+                    name: if self.capacity > 1 {
+                        format!("{name} {}", spot + 1)
+                    } else {
+                        name.clone()
+                    },
+                    group: self.group.clone(),
+                    tags: self.tags.clone(),
+                    ..Landmark::new(location, self.direction)
+                };
+                let offset =
+                    (spot as f32 - (self.capacity - 1) as f32 / 2.) * LANDMARK_SPOT_SPACING;
+                (landmark, center + Vec3::new(offset, 0., 0.))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub enum LandmarkFileError {
+    Io(std::io::Error),
+    Parse(ron::Error),
+}
+
+/// Last known modification of each landmark file.
+#[derive(Resource)]
+pub struct LandmarkFilesWatcher {
+    timer: Timer,
+    last_modified: HashMap<Location, SystemTime>,
+}
+
+impl Default for LandmarkFilesWatcher {
+    fn default() -> Self {
+        LandmarkFilesWatcher {
+            timer: Timer::from_seconds(LANDMARKS_RELOAD_DELAY, TimerMode::Repeating),
+            last_modified: HashMap::new(),
+        }
+    }
+}
+
+/// Parent of all landmarks of a location.
+#[derive(Component)]
+pub struct LandmarkZone(pub Location);
+
+/// Which landmark a npc would like to reserve.
+#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum LandmarkRequest {
+    Any,
+    Named(String),
+    Tagged(LandmarkTag),
+    InGroup(String),
+}
+
+impl LandmarkRequest {
+    pub fn matches(&self, landmark: &Landmark) -> bool {
+        match self {
+            LandmarkRequest::Any => !landmark.tags.contains(&LandmarkTag::CatNap),
+            LandmarkRequest::Named(name) => landmark.name == *name,
+            LandmarkRequest::Tagged(tag) => landmark.tags.contains(tag),
+            LandmarkRequest::InGroup(group) => landmark.group.as_ref() == Some(group),
         }
     }
 }
@@ -90,28 +233,83 @@ pub enum LandmarkReservationError {
     NoFreeLandmarks,
 }
 
-/// TODO: Create an impl to automatictly Reserved a free landmark
-///
-/// LandmarkReservationError
-pub fn reserved_random_free_landmark(
+/// Reserve, for the `npc`, a random free landmark in the `location` matching the `request`.
+pub fn reserve_free_landmark(
     landmark_sensor_query: &mut Query<(Entity, &mut Landmark), With<Sensor>>,
     location: Location,
+    request: &LandmarkRequest,
+    npc: Entity,
 ) -> Result<Entity, LandmarkReservationError> {
     match (*landmark_sensor_query)
         .iter_mut()
         .filter(|(_, landmark)| {
-            landmark.status == LandmarkStatus::Free && landmark.location == location
+            landmark.status == LandmarkStatus::Free
+                && landmark.location == location
+                && request.matches(landmark)
         })
         .choose(&mut rand::thread_rng())
     {
         None => Err(LandmarkReservationError::NoFreeLandmarks),
         Some((free_random_landmark, mut landmark)) => {
-            landmark.status = LandmarkStatus::Reserved;
+            landmark.status = LandmarkStatus::ReservedBy(npc);
+            landmark.reservation_timer.reset();
             Ok(free_random_landmark)
         }
     }
 }
 
+pub fn reserved_random_free_landmark(
+    landmark_sensor_query: &mut Query<(Entity, &mut Landmark), With<Sensor>>,
+    location: Location,
+    npc: Entity,
+) -> Result<Entity, LandmarkReservationError> {
+    reserve_free_landmark(landmark_sensor_query, location, &LandmarkRequest::Any, npc)
+}
+
+/// Give the behavior to seek a new free landmark,
+/// or `NPCBehavior::Camping` if there is none.
+pub fn seek_free_landmark(
+    landmark_sensor_query: &mut Query<(Entity, &mut Landmark), With<Sensor>>,
+    location: Location,
+    npc: Entity,
+) -> NPCBehavior {
+    match reserved_random_free_landmark(landmark_sensor_query, location, npc) {
+        Ok(landmark) => NPCBehavior::LandmarkSeeking(landmark, location),
+        Err(LandmarkReservationError::NoFreeLandmarks) => {
+            warn!("No free landmark in {location:?} for {npc:?}: camping");
+            NPCBehavior::Camping
+        }
+    }
+}
+
+/// Give the behavior to seek a free landmark matching the `request`,
+/// or any free landmark if there is none.
+pub fn seek_requested_landmark(
+    landmark_sensor_query: &mut Query<(Entity, &mut Landmark), With<Sensor>>,
+    location: Location,
+    request: &LandmarkRequest,
+    npc: Entity,
+) -> NPCBehavior {
+    match reserve_free_landmark(landmark_sensor_query, location, request, npc) {
+        Ok(landmark) => NPCBehavior::LandmarkSeeking(landmark, location),
+        Err(LandmarkReservationError::NoFreeLandmarks) => {
+            seek_free_landmark(landmark_sensor_query, location, npc)
+        }
+    }
+}
+
+/// Free the landmark reserved by the `npc` (if any).
+pub fn release_reservation(
+    landmark_sensor_query: &mut Query<(Entity, &mut Landmark), With<Sensor>>,
+    npc: Entity,
+) {
+    for (_, mut landmark) in landmark_sensor_query.iter_mut() {
+        if landmark.status == LandmarkStatus::ReservedBy(npc) {
+            landmark.status = LandmarkStatus::Free;
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct LandmarkGroup;
 
@@ -126,7 +324,10 @@ fn landmark_arrival(
 
     character_hitbox_query: Query<(Entity, &Parent, &Name), With<CharacterHitbox>>,
     mut npc_query: Query<(Entity, &mut NPCBehavior, &Name), With<NPC>>,
+    mut schedule_query: Query<&mut LandmarkSchedule>,
+    profile_query: Query<&AIProfile>,
     player_query: Query<Entity, With<Player>>,
+    location_query: Query<&Location>,
 
     mut landmark_sensor_query: Query<(Entity, &mut Landmark), With<Sensor>>,
     // parent_query: Query<&Parent>,
@@ -158,13 +359,11 @@ fn landmark_arrival(
                                     && landmark_destination == landmark_entity
                                 {
                                     // info!("This landmark {:?} was claimed before the NPC {:?} arrived", landmark_entity, **character_parent)
-                                    let next_destination = reserved_random_free_landmark(
+                                    *behavior = seek_free_landmark(
                                         &mut landmark_sensor_query,
                                         location,
-                                    )
-                                    .unwrap();
-                                    *behavior =
-                                        NPCBehavior::LandmarkSeeking(next_destination, location);
+                                        npc,
+                                    );
                                 } else if collision_event.is_stopped() && occupant == npc {
                                     landmark.status = LandmarkStatus::Free;
                                     commands.entity(**character_parent).remove::<Direction>();
@@ -182,21 +381,36 @@ fn landmark_arrival(
                                             .entity(**character_parent)
                                             .insert(forced_direction);
                                     }
-                                    // TODO: Or start dialog with the other
+                                    // Waits longer in a group, for the others to come and talk
                                     // info!(target: "Start Rest", "{:?}, {}", **character_parent, _name);
+                                    let rest_time = AIProfile::rest_time_of(
+                                        profile_query.get(npc).ok(),
+                                        landmark.tags.contains(&LandmarkTag::Gathering),
+                                    );
                                     commands.entity(**character_parent).insert(RestTime {
                                         timer: Timer::new(
-                                            Duration::from_secs(REST_TIMER),
+                                            Duration::from_secs(rest_time),
                                             TimerMode::Once,
                                         ),
                                     });
-                                    let next_destination = reserved_random_free_landmark(
-                                        &mut landmark_sensor_query,
-                                        location,
-                                    )
-                                    .unwrap();
-                                    *behavior =
-                                        NPCBehavior::LandmarkSeeking(next_destination, location);
+                                    // The next landmark of its schedule, if any
+                                    *behavior = match schedule_query
+                                        .get_mut(npc)
+                                        .ok()
+                                        .and_then(|mut schedule| schedule.next_request())
+                                    {
+                                        Some(request) => seek_requested_landmark(
+                                            &mut landmark_sensor_query,
+                                            location,
+                                            &request,
+                                            npc,
+                                        ),
+                                        None => seek_free_landmark(
+                                            &mut landmark_sensor_query,
+                                            location,
+                                            npc,
+                                        ),
+                                    };
                                 }
                             }
                         }
@@ -207,9 +421,26 @@ fn landmark_arrival(
                         LandmarkStatus::OccupiedBy(occupant) => {
                             if collision_event.is_stopped() && occupant == **character_parent {
                                 landmark.status = LandmarkStatus::Free;
+                            } else if collision_event.is_started() && occupant != **character_parent
+                            {
+                                // The player takes over the landmark: the occupant is bumped
+                                landmark.status = LandmarkStatus::OccupiedBy(**character_parent);
+                                if let Ok((npc, mut behavior, _npc_name)) =
+                                    npc_query.get_mut(occupant)
+                                {
+                                    commands.entity(npc).remove::<(RestTime, Direction)>();
+                                    if let Ok(location) = location_query.get(npc) {
+                                        release_reservation(&mut landmark_sensor_query, npc);
+                                        *behavior = seek_free_landmark(
+                                            &mut landmark_sensor_query,
+                                            *location,
+                                            npc,
+                                        );
+                                    }
+                                }
                             }
                         }
-                        LandmarkStatus::Free | LandmarkStatus::Reserved => {
+                        LandmarkStatus::Free | LandmarkStatus::ReservedBy(_) => {
                             if collision_event.is_started() {
                                 landmark.status = LandmarkStatus::OccupiedBy(**character_parent)
                             }
@@ -221,110 +452,216 @@ fn landmark_arrival(
     }
 }
 
+/* -------------------------------------------------------------------------- */
+/*                                Reservations                                */
+/* -------------------------------------------------------------------------- */
+
+/// Cancel the reservations never claimed in time
+/// (e.g. the npc is blocked, or rests elsewhere).
+///
+/// If the npc was still seeking this landmark, it will seek another one.
+fn reservation_timeout(
+    time: Res<Time>,
+    mut landmark_sensor_query: Query<(Entity, &mut Landmark), With<Sensor>>,
+    mut npc_query: Query<&mut NPCBehavior, With<NPC>>,
+) {
+    let mut expired_reservations = Vec::new();
+    for (landmark_entity, mut landmark) in &mut landmark_sensor_query {
+        if let LandmarkStatus::ReservedBy(npc) = landmark.status {
+            landmark.reservation_timer.tick(time.delta());
+            if landmark.reservation_timer.finished() {
+                expired_reservations.push((landmark_entity, npc));
+            }
+        }
+    }
+
+    for (landmark_entity, npc) in expired_reservations {
+        if let Ok(mut behavior) = npc_query.get_mut(npc) {
+            if let NPCBehavior::LandmarkSeeking(destination, location) = *behavior {
+                if destination == landmark_entity {
+                    // choose the next one before freeing this one
+                    *behavior = seek_free_landmark(&mut landmark_sensor_query, location, npc);
+                }
+            }
+        }
+
+        if let Ok((_, mut landmark)) = landmark_sensor_query.get_mut(landmark_entity) {
+            landmark.status = LandmarkStatus::Free;
+        }
+    }
+}
+
+/// Free all landmarks reserved or occupied by a despawned npc.
+fn release_landmarks_on_despawn(
+    mut removed_npcs: RemovedComponents<NPC>,
+    mut landmark_sensor_query: Query<&mut Landmark, With<Sensor>>,
+) {
+    for npc in removed_npcs.iter() {
+        for mut landmark in &mut landmark_sensor_query {
+            if landmark.status == LandmarkStatus::ReservedBy(npc)
+                || landmark.status == LandmarkStatus::OccupiedBy(npc)
+            {
+                landmark.status = LandmarkStatus::Free;
+            }
+        }
+    }
+}
+
+/// Landmarks are kept between two games, not their occupants.
+fn free_all_landmarks(mut landmark_query: Query<&mut Landmark>) {
+    for mut landmark in &mut landmark_query {
+        landmark.status = LandmarkStatus::Free;
+        landmark.reservation_timer.reset();
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 /*                               Spawn Landmarks                              */
 /* -------------------------------------------------------------------------- */
 
-fn spawn_landmarks(mut commands: Commands) {
+fn landmark_file_path(location: Location) -> PathBuf {
+    let file_name = match location {
+        Location::Hall => HALL_LANDMARKS_FILE,
+        Location::Temple => TEMPLE_LANDMARKS_FILE,
+        Location::SecretRoom => SECRET_ROOM_LANDMARKS_FILE,
+    };
+    Path::new(LANDMARKS_DIRECTORY).join(file_name)
+}
+
+fn load_landmark_file(path: &Path) -> Result<LandmarkFile, LandmarkFileError> {
+    let content = fs::read_to_string(path).map_err(LandmarkFileError::Io)?;
+    ron::from_str(&content).map_err(LandmarkFileError::Parse)
+}
+
+fn spawn_landmarks(mut commands: Commands, mut watcher: ResMut<LandmarkFilesWatcher>) {
     commands
         .spawn((TransformBundle::default(), Name::new("Landmarks")))
         .with_children(|parent| {
-            let landmark_sensor = (
-                Collider::ball(LANDMARK_SENSOR_SIZE),
-                ActiveEvents::COLLISION_EVENTS,
-                Sensor,
-            );
+            for location in [Location::Hall, Location::Temple, Location::SecretRoom] {
+                let path = landmark_file_path(location);
+                let landmark_file = match load_landmark_file(&path) {
+                    Ok(landmark_file) => landmark_file,
+                    Err(error) => {
+                        error!("Invalid landmark file {}: {error:?}", path.display());
+                        LandmarkFile::default()
+                    }
+                };
+                if let Ok(modified) = fs::metadata(&path).and_then(|metadata| metadata.modified()) {
+                    watcher.last_modified.insert(location, modified);
+                }
+
+                spawn_zone_landmarks(parent, location, landmark_file);
+            }
+        });
+}
 
-            let mut secret_room_singletons = secret_room::landmarks::LANDMARK_SINGLETONS.to_vec();
-            secret_room_singletons.extend(secret_room::landmarks::LANDMARKS_FLOWER_PANELS.to_vec());
+/// Respawn the landmarks of a location when its file is modified.
+///
+/// All reservations of this location are lost:
+/// npcs seeking one of the old landmarks will seek a new one.
+///
+/// Only polled while playing: a file modified meanwhile is reloaded once back in the game.
+fn hot_reload_landmarks(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut watcher: ResMut<LandmarkFilesWatcher>,
+    zone_query: Query<(Entity, &LandmarkZone, &Parent)>,
+) {
+    watcher.timer.tick(time.delta());
+    if !watcher.timer.just_finished() {
+        return;
+    }
 
-            let zone_landmarks = [
-                (
-                    "Hall",
-                    Location::Hall,
-                    hall::landmarks::LANDMARK_GROUPS.to_vec(),
-                    hall::landmarks::LANDMARK_SINGLETONS.to_vec(),
-                ),
-                (
-                    "Temple",
-                    Location::Temple,
-                    main_room::landmarks::LANDMARK_GROUPS.to_vec(),
-                    main_room::landmarks::LANDMARK_SINGLETONS.to_vec(),
-                ),
-                (
-                    "SecretRoom",
-                    Location::SecretRoom,
-                    Vec::new(),
-                    secret_room_singletons,
-                ),
-            ];
+    for (zone, LandmarkZone(location), landmarks_root) in &zone_query {
+        let path = landmark_file_path(*location);
+        let Ok(modified) = fs::metadata(&path).and_then(|metadata| metadata.modified()) else {
+            continue;
+        };
+        if watcher.last_modified.get(location) == Some(&modified) {
+            continue;
+        }
+        watcher.last_modified.insert(*location, modified);
 
-            for (name, location, groups, singletons) in zone_landmarks {
-                parent
-                    .spawn((TransformBundle::default(), Name::new(name)))
-                    .with_children(|parent| {
-                        // ----- Groups -----
-                        for (group, group_name) in groups {
-                            parent
-                                .spawn((
-                                    LandmarkGroup,
-                                    TransformBundle::default(),
-                                    Name::new(format!("{group_name} Discussion Group")),
-                                ))
-                                .with_children(|parent| {
-                                    for (position, landmark_name, landmark_direction) in group {
-                                        parent.spawn((
-                                            Landmark::new(location, landmark_direction),
-                                            TransformBundle::from_transform(
-                                                Transform::from_translation(position.into()),
-                                            ),
-                                            Name::new(format!(
-                                                "Landmark {group_name} {landmark_name}"
-                                            )),
-                                            landmark_sensor.clone(),
-                                        ));
-                                    }
-                                });
-                        }
+        match load_landmark_file(&path) {
+            Err(error) => warn!("Landmark file {} not reloaded: {error:?}", path.display()),
+            Ok(landmark_file) => {
+                info!("Reload {}", path.display());
+                commands.entity(zone).despawn_recursive();
+                commands
+                    .entity(**landmarks_root)
+                    .with_children(|parent| spawn_zone_landmarks(parent, *location, landmark_file));
+            }
+        }
+    }
+}
 
-                        // ----- Singletons -----
-                        for (position, landmark_name, landmark_direction) in singletons {
-                            parent.spawn((
-                                Landmark::new(location, landmark_direction),
-                                TransformBundle::from_transform(Transform::from_translation(
-                                    position.into(),
-                                )),
-                                Name::new(format!("Landmark {landmark_name}")),
-                                landmark_sensor.clone(),
-                            ));
-                        }
+fn spawn_zone_landmarks(
+    parent: &mut ChildBuilder,
+    location: Location,
+    landmark_file: LandmarkFile,
+) {
+    let landmark_sensor = (
+        Collider::ball(LANDMARK_SENSOR_SIZE),
+        ActiveEvents::COLLISION_EVENTS,
+        Sensor,
+    );
 
-                        if location == Location::Temple {
-                            for (landmarks, pillar_name) in main_room::landmarks::LANDMARK_PILLARS {
-                                parent
-                                    .spawn((
-                                        TransformBundle::default(),
-                                        Name::new(format!("{pillar_name}'s landmarks")),
-                                    ))
-                                    .with_children(|parent| {
-                                        for (position, landmark_name, landmark_direction) in
-                                            landmarks
-                                        {
-                                            parent.spawn((
-                                                Landmark::new(location, landmark_direction),
-                                                TransformBundle::from_transform(
-                                                    Transform::from_translation(position.into()),
-                                                ),
-                                                Name::new(format!(
-                                                    "Landmark {pillar_name} {landmark_name}"
-                                                )),
-                                                landmark_sensor.clone(),
-                                            ));
-                                        }
-                                    });
+    // keep the order of the file
+    let mut groups: Vec<(String, Vec<LandmarkDefinition>)> = Vec::new();
+    let mut singletons = Vec::new();
+    for definition in landmark_file.landmarks {
+        match &definition.group {
+            None => singletons.push(definition),
+            Some(group_name) => match groups.iter_mut().find(|(name, _)| name == group_name) {
+                Some((_, group)) => group.push(definition),
+                None => groups.push((group_name.clone(), vec![definition])),
+            },
+        }
+    }
+
+    parent
+        .spawn((
+            LandmarkZone(location),
+            TransformBundle::default(),
+            Name::new(format!("{location:?}")),
+        ))
+        .with_children(|parent| {
+            // ----- Groups -----
+            for (group_name, group) in groups {
+                parent
+                    .spawn((
+                        LandmarkGroup,
+                        TransformBundle::default(),
+                        Name::new(format!("{group_name} Discussion Group")),
+                    ))
+                    .with_children(|parent| {
+                        for definition in group {
+                            for (landmark, position) in definition.landmarks(location) {
+                                let name = format!("Landmark {}", landmark.name);
+                                parent.spawn((
+                                    landmark,
+                                    TransformBundle::from_transform(Transform::from_translation(
+                                        position,
+                                    )),
+                                    Name::new(name),
+                                    landmark_sensor.clone(),
+                                ));
                             }
                         }
                     });
             }
+
+            // ----- Singletons -----
+            for definition in singletons {
+                for (landmark, position) in definition.landmarks(location) {
+                    let name = format!("Landmark {}", landmark.name);
+                    parent.spawn((
+                        landmark,
+                        TransformBundle::from_transform(Transform::from_translation(position)),
+                        Name::new(name),
+                        landmark_sensor.clone(),
+                    ));
+                }
+            }
         });
 }
@@ -20,6 +20,7 @@ use crate::{
         Chandelier, DoorColliderClosed, DoorState, Flame, Location, LocationSensor,
         OverlappingEntity, WallCollider,
     },
+    GameState, StateScoped,
 };
 
 use super::DoorColliderOpened;
@@ -208,6 +209,7 @@ pub fn setup_hall(
             Hall,
             RigidBody::Fixed,
             Name::new("Hall"),
+            StateScoped(GameState::Playing),
         ))
         .with_children(|parent| {
             parent.spawn((
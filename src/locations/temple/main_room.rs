@@ -16,6 +16,7 @@ use crate::{
         Chandelier, DoorColliderClosed, DoorState, Flame, Location, LocationSensor,
         OverlappingEntity, WallCollider,
     },
+    GameState, StateScoped,
 };
 
 /* -------------------------------------------------------------------------- */
@@ -177,6 +178,7 @@ pub fn setup_main_room(
             Temple,
             RigidBody::Fixed,
             Name::new("Temple"),
+            StateScoped(GameState::Playing),
         ))
         .with_children(|parent| {
             // --- Temple Sensors ---
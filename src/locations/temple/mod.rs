@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::{CollisionEvent, Sensor};
+use serde::{Deserialize, Serialize};
 
 use crate::{
     characters::player::{Player, PlayerHitbox},
@@ -19,7 +20,9 @@ pub mod secret_room;
 #[derive(Component, Deref, DerefMut)]
 pub struct ZPosition(f32);
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Default, Reflect, Component)]
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug, Hash, Default, Reflect, Component,
+)]
 pub enum Location {
     #[default]
     Hall,
@@ -263,7 +266,7 @@ fn control_wall_collider(
 /*                               Door Animation                               */
 /* -------------------------------------------------------------------------- */
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Component)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug, Component)]
 pub enum DoorState {
     Opened,
     Closed,
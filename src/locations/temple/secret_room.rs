@@ -11,6 +11,7 @@ use crate::{
     collisions::{TesselatedCollider, TesselatedColliderConfig},
     constants::{locations::secret_room::*, BACKGROUND_COLOR_INGAME},
     locations::temple::{Location, LocationSensor, OverlappingEntity, WallCollider},
+    GameState, StateScoped,
 };
 
 /* -------------------------------------------------------------------------- */
@@ -273,6 +274,7 @@ pub fn setup_secret_room(
             SecretRoom,
             RigidBody::Fixed,
             Name::new("Secret Room"),
+            StateScoped(GameState::Playing),
         ))
         .with_children(|parent| {
             // TEMP: Indicators
@@ -437,5 +439,6 @@ pub fn setup_secret_room(
         },
         SecretRoomCover,
         Name::new("Secret Room Cover"),
+        StateScoped(GameState::Playing),
     ));
 }
@@ -11,6 +11,8 @@ pub mod dialogs;
 pub mod interactions;
 mod locations;
 mod menu;
+pub mod pause;
+pub mod save;
 mod ui;
 
 use std::time::Duration;
@@ -22,7 +24,6 @@ use bevy_rapier2d::prelude::*;
 
 use crate::{
     constants::{BACKGROUND_COLOR_INGAME, BACKGROUND_COLOR_INMENU},
-    controls::Key,
     dialogs::{DialogId, Dialogs, Language},
 };
 
@@ -46,6 +47,33 @@ pub enum HUDState {
     OptionsWall,
 }
 
+/// Only while `GameState::Playing`.
+#[derive(Clone, Eq, PartialEq, Debug, Hash, Default, Reflect, States)]
+pub enum PauseState {
+    #[default]
+    Running,
+    /// Physics, timers and animations are frozen.
+    Paused,
+}
+
+/// Despawned (recursively) when leaving this `GameState`.
+///
+/// Everything spawned `OnEnter` a state must have it, for the state to be entered again
+/// (e.g. Playing -> Menu -> Playing).
+/// The resources changed while playing are reset `OnExit(GameState::Playing)` by their plugin.
+#[derive(Component)]
+pub struct StateScoped(pub GameState);
+
+/// From 0 to 1.
+#[derive(Resource)]
+pub struct MusicVolume(pub f32);
+
+impl Default for MusicVolume {
+    fn default() -> Self {
+        MusicVolume(0.10)
+    }
+}
+
 #[derive(Component)]
 struct PlayerCamera;
 
@@ -57,13 +85,6 @@ fn main() {
 
     app.insert_resource(Msaa::Off)
         .insert_resource(ClearColor(BACKGROUND_COLOR_INMENU))
-        .insert_resource(controls::KeyBindings {
-            up: [Key(KeyCode::W), Key(KeyCode::Z), Key(KeyCode::Up)],
-            down: [Key(KeyCode::S), Key(KeyCode::Down)],
-            right: [Key(KeyCode::D), Key(KeyCode::Right)],
-            left: [Key(KeyCode::A), Key(KeyCode::Q), Key(KeyCode::Left)],
-            interact: [Key(KeyCode::E), Key(KeyCode::R)],
-        })
         .add_plugins((
             DefaultPlugins
                 .set(WindowPlugin {
@@ -84,6 +105,7 @@ fn main() {
             RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(1.),
             // ----- Our plugins -----
             animations::AnimationPlugin,
+            controls::ControlsPlugin,
             dialogs::DialogsPlugin,
             debug::DebugPlugin,
             collisions::CollisionsPlugin,
@@ -93,11 +115,26 @@ fn main() {
             characters::CharactersPlugin,
             combat::CombatPlugin,
             ui::UiPlugin,
+            save::SavePlugin,
+            pause::PausePlugin,
         ))
         .add_state::<GameState>()
         .add_state::<HUDState>()
+        .add_state::<PauseState>()
+        .init_resource::<MusicVolume>()
         .add_systems(Startup, (game_setup, music))
-        .add_systems(OnEnter(GameState::Playing), setup_background_playing);
+        .add_systems(OnEnter(GameState::Playing), setup_background_playing)
+        .add_systems(
+            OnExit(GameState::Menu),
+            despawn_state_scoped(GameState::Menu),
+        )
+        .add_systems(
+            OnExit(GameState::Playing),
+            (
+                despawn_state_scoped(GameState::Playing),
+                setup_background_menu,
+            ),
+        );
 
     app.edit_schedule(Main, |schedule| {
         schedule.set_build_settings(ScheduleBuildSettings {
@@ -121,16 +158,32 @@ fn setup_background_playing(mut clear_color: ResMut<ClearColor>) {
     clear_color.0 = BACKGROUND_COLOR_INGAME;
 }
 
+fn setup_background_menu(mut clear_color: ResMut<ClearColor>) {
+    clear_color.0 = BACKGROUND_COLOR_INMENU;
+}
+
+/// Run on `OnExit(state)`: the `State` is already the next one.
+fn despawn_state_scoped(state: GameState) -> impl FnMut(Commands, Query<(Entity, &StateScoped)>) {
+    move |mut commands, scoped_query| {
+        for (entity, StateScoped(scope)) in &scoped_query {
+            if *scope == state {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
 /// Marker component for our music entity
 #[derive(Component)]
 struct CastleTheme;
 
-fn music(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn music(mut commands: Commands, asset_server: Res<AssetServer>, music_volume: Res<MusicVolume>) {
     commands.spawn((
         AudioBundle {
             source: asset_server.load("sounds/FTO_Dracula_theme.ogg"),
-            settings: PlaybackSettings::LOOP
-                .with_volume(bevy::audio::Volume::Relative(VolumeLevel::new(0.10))),
+            settings: PlaybackSettings::LOOP.with_volume(bevy::audio::Volume::Relative(
+                VolumeLevel::new(music_volume.0),
+            )),
         },
         CastleTheme,
     ));
@@ -157,3 +210,7 @@ pub fn hud_closed(hud_state: Res<State<HUDState>>) -> bool {
 pub fn hud_opened(hud_state: Res<State<HUDState>>) -> bool {
     hud_state.get() != &HUDState::Closed
 }
+
+pub fn paused(pause_state: Res<State<PauseState>>) -> bool {
+    pause_state.get() == &PauseState::Paused
+}
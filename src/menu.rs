@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use crate::{
     animations::sprite_sheet_animation::{AnimationDuration, SpriteSheetAnimation},
-    in_menu, DialogId, Dialogs, GameState, Language,
+    in_menu, DialogId, Dialogs, GameState, Language, StateScoped,
 };
 use bevy::{input::keyboard::KeyboardInput, prelude::*, window::WindowResized};
 use rand::{
@@ -18,7 +18,6 @@ impl Plugin for MenuPlugin {
         app.add_event::<LanguageChangedEvent>()
             .init_resource::<LanguagesButtonColors>()
             .add_systems(OnEnter(GameState::Menu), setup_menu)
-            .add_systems(OnExit(GameState::Menu), destroy_menu)
             .add_systems(
                 Update,
                 (game_start, language_button_interactions, language_changed).run_if(in_menu),
@@ -26,9 +25,6 @@ impl Plugin for MenuPlugin {
             .add_systems(PostUpdate, adjust_art_height.run_if(in_menu));
     }
 }
-#[derive(Component)]
-struct Menu;
-
 #[derive(Component)]
 struct ArtMenu;
 
@@ -106,12 +102,6 @@ impl Distribution<ManorLightsPattern> for Standard {
 /*                                   Systems                                  */
 /* -------------------------------------------------------------------------- */
 
-fn destroy_menu(mut commands: Commands, mut query: Query<Entity, With<Menu>>) {
-    for entity in query.iter_mut() {
-        commands.entity(entity).despawn_recursive();
-    }
-}
-
 fn game_start(
     mut keyboard_inputs: EventReader<KeyboardInput>,
     game_state: Res<State<GameState>>,
@@ -288,7 +278,7 @@ fn setup_menu(
                 ..default()
             },
             Name::new("Menu"),
-            Menu,
+            StateScoped(GameState::Menu),
         ))
         .with_children(|parent| {
             parent
@@ -468,7 +458,7 @@ fn setup_menu(
                                     background_color: Color::NONE.into(),
                                     ..default()
                                 },
-                                Selected(Language::default() == language),
+                                Selected(*current_language == language),
                                 language,
                                 Name::new(format!("{}", language)),
                             ))
@@ -0,0 +1,224 @@
+//! Pause menu, on `PauseState::Paused`
+//!
+//! `PAUSE_KEY` pauses the game while no hud is opened.
+//! The physics, the timers and the animations (all driven by `Time`) are frozen
+//! and the music is ducked.
+//!
+//! The options are opened above the pause menu; `PAUSE_KEY` closes them.
+
+use bevy::{audio::AudioSinkPlayback, prelude::*, ui::FocusPolicy};
+use bevy_rapier2d::prelude::RapierConfiguration;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter};
+
+use crate::{
+    constants::{
+        save::QUICK_SAVE_SLOT,
+        ui::{
+            options::{OPTIONS_HOVERED_BUTTON, OPTIONS_NORMAL_BUTTON, OPTIONS_ROW_GAP},
+            pause::*,
+        },
+    },
+    hud_closed,
+    save::SaveEvent,
+    ui::options::{OptionsResources, Rebinding},
+    CastleTheme, GameState, HUDState, MusicVolume, PauseState,
+};
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            toggle_pause_on_key_press
+                .before(crate::ui::options::rebind_key)
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            OnEnter(PauseState::Paused),
+            (freeze_world, create_pause_menu),
+        )
+        .add_systems(
+            Update,
+            (pause_menu_buttons.run_if(hud_closed), update_pause_buttons)
+                .run_if(in_state(PauseState::Paused)),
+        )
+        .add_systems(
+            OnExit(PauseState::Paused),
+            (unfreeze_world, close_pause_menu),
+        )
+        .add_systems(OnExit(GameState::Playing), leave_pause)
+        .add_systems(Update, apply_music_volume);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Component)]
+pub struct PauseMenu;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumIter, Component)]
+pub enum PauseButton {
+    Resume,
+    Options,
+    /// In the quick save slot.
+    Save,
+    #[strum(serialize = "Quit to title")]
+    QuitToTitle,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+/// Pauses or resumes the game, if no other hud is opened.
+/// Closes the options instead, unless a key is being rebound (`PAUSE_KEY` cancels it).
+pub fn toggle_pause_on_key_press(
+    keyboard_input: Res<Input<KeyCode>>,
+    rebinding: Res<Rebinding>,
+    hud_state: Res<State<HUDState>>,
+    mut next_hud_state: ResMut<NextState<HUDState>>,
+    pause_state: Res<State<PauseState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    if !keyboard_input.just_pressed(PAUSE_KEY) || rebinding.0.is_some() {
+        return;
+    }
+    match (hud_state.get(), pause_state.get()) {
+        (HUDState::OptionsWall, _) => next_hud_state.set(HUDState::Closed),
+        (HUDState::Closed, PauseState::Running) => next_pause_state.set(PauseState::Paused),
+        (HUDState::Closed, PauseState::Paused) => next_pause_state.set(PauseState::Running),
+        _ => {}
+    }
+}
+
+pub fn freeze_world(mut time: ResMut<Time>, mut rapier_config: ResMut<RapierConfiguration>) {
+    time.pause();
+    rapier_config.physics_pipeline_active = false;
+}
+
+pub fn unfreeze_world(mut time: ResMut<Time>, mut rapier_config: ResMut<RapierConfiguration>) {
+    time.unpause();
+    rapier_config.physics_pipeline_active = true;
+}
+
+/// Leaving the game, by the pause menu or by any other way, leaves the pause and the huds.
+pub fn leave_pause(
+    mut next_hud_state: ResMut<NextState<HUDState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    next_hud_state.set(HUDState::Closed);
+    next_pause_state.set(PauseState::Running);
+}
+
+/// The music is ducked while paused.
+pub fn apply_music_volume(
+    music_volume: Res<MusicVolume>,
+    pause_state: Res<State<PauseState>>,
+    music_query: Query<Ref<AudioSink>, With<CastleTheme>>,
+) {
+    for sink in &music_query {
+        if !sink.is_added() && !music_volume.is_changed() && !pause_state.is_changed() {
+            continue;
+        }
+        let ducking = match pause_state.get() {
+            PauseState::Running => 1.,
+            PauseState::Paused => PAUSED_MUSIC_DUCKING,
+        };
+        sink.set_volume(music_volume.0 * ducking);
+    }
+}
+
+pub fn create_pause_menu(mut commands: Commands, options_resources: Res<OptionsResources>) {
+    let text_style = options_resources.text_style();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(OPTIONS_ROW_GAP),
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: PAUSE_BACKGROUND.into(),
+                // The world behind cannot be clicked
+                focus_policy: FocusPolicy::Block,
+                ..default()
+            },
+            PauseMenu,
+            Name::new("Pause Menu"),
+        ))
+        .with_children(|parent| {
+            for button in PauseButton::iter() {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(PAUSE_BUTTON_WIDTH),
+                                justify_content: JustifyContent::Center,
+                                padding: UiRect::all(Val::Px(OPTIONS_ROW_GAP / 2.)),
+                                ..default()
+                            },
+                            background_color: OPTIONS_NORMAL_BUTTON.into(),
+                            ..default()
+                        },
+                        button,
+                        Name::new(format!("{button} Button")),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            button.to_string(),
+                            text_style.clone(),
+                        ));
+                    });
+            }
+        });
+}
+
+pub fn close_pause_menu(mut commands: Commands, menu_query: Query<Entity, With<PauseMenu>>) {
+    for menu in &menu_query {
+        commands.entity(menu).despawn_recursive();
+    }
+}
+
+/// Only while the options are closed: they are above the pause menu.
+pub fn pause_menu_buttons(
+    button_query: Query<(&Interaction, &PauseButton), Changed<Interaction>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_hud_state: ResMut<NextState<HUDState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+    mut save_event: EventWriter<SaveEvent>,
+) {
+    for (interaction, button) in &button_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            PauseButton::Resume => next_pause_state.set(PauseState::Running),
+            PauseButton::Options => next_hud_state.set(HUDState::OptionsWall),
+            PauseButton::Save => save_event.send(SaveEvent {
+                slot: QUICK_SAVE_SLOT,
+            }),
+            PauseButton::QuitToTitle => next_game_state.set(GameState::Menu),
+        }
+    }
+}
+
+pub fn update_pause_buttons(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), With<PauseButton>>,
+) {
+    for (interaction, mut color) in &mut button_query {
+        *color = match interaction {
+            Interaction::Hovered | Interaction::Pressed => OPTIONS_HOVERED_BUTTON.into(),
+            Interaction::None => OPTIONS_NORMAL_BUTTON.into(),
+        };
+    }
+}
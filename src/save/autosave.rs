@@ -0,0 +1,133 @@
+//! Autosave checkpoints
+//!
+//! Changing room, entering a combat or triggering a dialog event asks for an autosave.
+//! It is taken as soon as no hud is opened (right away for a combat, which has not started yet;
+//! at the end of the dialog for a dialog event),
+//! in the oldest of the `AUTOSAVE_SLOTS`, and written on a background task.
+
+use bevy::{
+    prelude::*,
+    tasks::{futures_lite::future, IoTaskPool, Task},
+};
+use std::{fs, time::SystemTime};
+
+use crate::{
+    characters::player::Player, combat::CombatEvent, constants::save::AUTOSAVE_SLOTS,
+    locations::temple::Location, ui::dialog_systems::TriggerEvents,
+};
+
+use super::{slot_path, write_save, SaveError, WorldSnapshot};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Checkpoint {
+    RoomChange,
+    Combat,
+    DialogTrigger,
+}
+
+#[derive(Resource)]
+pub struct Autosaves {
+    /// The checkpoint waiting for the hud to be closed, if any.
+    pub requested: Option<Checkpoint>,
+    /// Index in `AUTOSAVE_SLOTS`.
+    next: usize,
+    /// Slot being written.
+    writing: Option<(usize, Task<Result<(), SaveError>>)>,
+}
+
+impl Default for Autosaves {
+    /// Starts by the oldest autosave slot (or an empty one).
+    fn default() -> Self {
+        let next = AUTOSAVE_SLOTS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, slot)| {
+                slot_path(**slot)
+                    .and_then(|path| fs::metadata(path).ok())
+                    .and_then(|metadata| metadata.modified().ok())
+                    .unwrap_or(SystemTime::UNIX_EPOCH)
+            })
+            .map_or(0, |(index, _)| index);
+
+        Autosaves {
+            requested: None,
+            next,
+            writing: None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+pub fn checkpoint_on_room_change(
+    mut autosaves: ResMut<Autosaves>,
+    player_query: Query<Ref<Location>, With<Player>>,
+) {
+    if let Ok(location) = player_query.get_single() {
+        if location.is_changed() && !location.is_added() {
+            autosaves.requested = Some(Checkpoint::RoomChange);
+        }
+    }
+}
+
+/// Taken in the same frame, before the combat freezes anyone.
+pub fn checkpoint_before_combat(
+    mut autosaves: ResMut<Autosaves>,
+    mut ev_combat: EventReader<CombatEvent>,
+) {
+    if ev_combat.iter().count() > 0 {
+        autosaves.requested = Some(Checkpoint::Combat);
+    }
+}
+
+pub fn checkpoint_after_dialog_triggers(
+    mut autosaves: ResMut<Autosaves>,
+    mut trigger_event: EventReader<TriggerEvents>,
+) {
+    if trigger_event.iter().count() > 0 {
+        autosaves.requested = Some(Checkpoint::DialogTrigger);
+    }
+}
+
+/// Runs only while the hud is closed.
+/// A checkpoint asked while the previous autosave is still written waits for it.
+pub fn autosave(mut autosaves: ResMut<Autosaves>, world_snapshot: WorldSnapshot) {
+    if autosaves.writing.is_some() {
+        return;
+    }
+    let Some(checkpoint) = autosaves.requested.take() else {
+        return;
+    };
+    let Some(save) = world_snapshot.take() else {
+        return;
+    };
+
+    let slot = AUTOSAVE_SLOTS[autosaves.next];
+    autosaves.next = (autosaves.next + 1) % AUTOSAVE_SLOTS.len();
+    info!("Autosave ({checkpoint:?}) in slot {slot}");
+
+    let task = IoTaskPool::get().spawn(async move { write_save(slot, &save) });
+    autosaves.writing = Some((slot, task));
+}
+
+/// Occurs `OnExit(GameState::Playing)`
+///
+/// An autosave being written is kept.
+pub fn cancel_checkpoint(mut autosaves: ResMut<Autosaves>) {
+    autosaves.requested = None;
+}
+
+pub fn poll_autosave(mut autosaves: ResMut<Autosaves>) {
+    let Some((slot, task)) = &mut autosaves.writing else {
+        return;
+    };
+    let Some(result) = future::block_on(future::poll_once(task)) else {
+        return;
+    };
+    if let Err(error) = result {
+        error!("Could not autosave in slot {slot}: {error:?}");
+    }
+    autosaves.writing = None;
+}
@@ -0,0 +1,530 @@
+//! Save slots
+//!
+//! A save is a RON file (`slot_<n>.ron`) in the data directory of the platform
+//! (`~/.local/share/fabien-et-la-trahison-de-olf/saves/` on Linux).
+//!
+//! Entities change between two runs: npcs, landmarks and follow targets are saved by `Name`.
+//!
+//! Each save starts with its `version`.
+//! An older save is upgraded, step by step, by the `MIGRATIONS` before being read.
+//!
+//! The slot `QUICK_SAVE_SLOT` is the quick save, the `AUTOSAVE_SLOTS` are the autosaves.
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_rapier2d::prelude::Sensor;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+use crate::{
+    characters::{
+        npcs::{movement::NPCBehavior, NPCSystems, NPC},
+        player::Player,
+    },
+    combat::{Recruted, Reputation},
+    constants::{controls::CONFIG_DIRECTORY, save::*},
+    controls::{Action, ActionInput},
+    hud_closed,
+    locations::{
+        landmarks::{
+            release_reservation, seek_requested_landmark, Landmark, LandmarkRequest, LandmarkStatus,
+        },
+        temple::{
+            hall::TempleDoor,
+            main_room::SecretBanner,
+            secret_room::{AddSecretRoomCoverEvent, RemoveSecretRoomCoverEvent, SecretRoomCover},
+            DoorInteractEvent, DoorState, Location,
+        },
+    },
+    ui::dialog_systems::{ActiveWorldEvents, DialogMap, WorldEvent},
+    GameState,
+};
+
+pub mod autosave;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SaveEvent>()
+            .add_event::<LoadEvent>()
+            .init_resource::<autosave::Autosaves>()
+            .add_systems(
+                Update,
+                (
+                    quick_save.run_if(in_state(GameState::Playing)),
+                    quick_load,
+                    save_game.run_if(in_state(GameState::Playing)),
+                    load_game,
+                    apply_pending_load
+                        .run_if(in_state(GameState::Playing))
+                        .run_if(resource_exists::<PendingLoad>()),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    (
+                        autosave::checkpoint_on_room_change,
+                        autosave::checkpoint_before_combat.after(NPCSystems::Collision),
+                        autosave::checkpoint_after_dialog_triggers,
+                    ),
+                    autosave::autosave.run_if(hud_closed),
+                    autosave::poll_autosave,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), autosave::cancel_checkpoint);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Save Format                                */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SaveGame {
+    pub version: u32,
+    pub player: SavedCharacter,
+    pub npcs: Vec<SavedNPC>,
+    /// (interlocutor name, current state)
+    pub dialogs: Vec<(String, usize)>,
+    pub world_events: Vec<WorldEvent>,
+    pub temple_door: DoorState,
+    pub secret_banner: DoorState,
+    pub secret_room_cover_removed: bool,
+    /// Names of the recruited npcs.
+    pub party: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SavedCharacter {
+    pub position: (f32, f32),
+    pub location: Location,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SavedNPC {
+    pub name: String,
+    pub character: SavedCharacter,
+    pub behavior: SavedBehavior,
+    pub reputation: Reputation,
+}
+
+/// `NPCBehavior` without entities.
+#[derive(Serialize, Deserialize, Debug)]
+pub enum SavedBehavior {
+    Camping,
+    LandmarkSeeking {
+        landmark: String,
+        location: Location,
+    },
+    Follow {
+        target: String,
+    },
+}
+
+/// Only the version, to know which migrations a save needs.
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+/// `MIGRATIONS[n]` upgrades the content of a save from the version `n + 1` to `n + 2`.
+const MIGRATIONS: &[fn(String) -> Result<String, SaveError>] = &[];
+
+#[derive(Debug)]
+pub enum SaveError {
+    NoDataDirectory,
+    Io(std::io::Error),
+    Parse(ron::Error),
+    Serialize(ron::Error),
+    /// Made by a newer version of the game.
+    UnknownVersion(u32),
+}
+
+/// `None` if the platform has no data directory (wasm).
+pub fn slot_path(slot: usize) -> Option<PathBuf> {
+    dirs::data_dir().map(|data_dir| {
+        data_dir
+            .join(CONFIG_DIRECTORY)
+            .join(SAVES_DIRECTORY)
+            .join(format!("slot_{slot}.ron"))
+    })
+}
+
+fn migrate(mut content: String) -> Result<String, SaveError> {
+    let SaveHeader { mut version } = ron::from_str(&content).map_err(SaveError::Parse)?;
+    while version < SAVE_VERSION {
+        let migration = (version as usize)
+            .checked_sub(1)
+            .and_then(|index| MIGRATIONS.get(index))
+            .ok_or(SaveError::UnknownVersion(version))?;
+        content = migration(content)?;
+        info!("Save migrated from v{version} to v{}", version + 1);
+        version += 1;
+    }
+    if version > SAVE_VERSION {
+        return Err(SaveError::UnknownVersion(version));
+    }
+    Ok(content)
+}
+
+pub fn read_save(slot: usize) -> Result<SaveGame, SaveError> {
+    let path = slot_path(slot).ok_or(SaveError::NoDataDirectory)?;
+    let content = fs::read_to_string(path).map_err(SaveError::Io)?;
+    let content = migrate(content)?;
+    ron::from_str(&content).map_err(SaveError::Parse)
+}
+
+pub fn write_save(slot: usize, save: &SaveGame) -> Result<(), SaveError> {
+    let path = slot_path(slot).ok_or(SaveError::NoDataDirectory)?;
+    let content = ron::ser::to_string_pretty(save, ron::ser::PrettyConfig::default())
+        .map_err(SaveError::Serialize)?;
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(SaveError::Io)?;
+    }
+    // A save interrupted while written leaves the previous one intact
+    let temporary_path = path.with_extension("tmp");
+    fs::write(&temporary_path, content).map_err(SaveError::Io)?;
+    fs::rename(temporary_path, path).map_err(SaveError::Io)
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+/// A save read, waiting for the world of `GameState::Playing` to be restored.
+#[derive(Resource)]
+pub struct PendingLoad(pub SaveGame);
+
+/* -------------------------------------------------------------------------- */
+/*                                   Events                                   */
+/* -------------------------------------------------------------------------- */
+
+/// Happens when:
+///   - save::quick_save
+///     - `Action::QuickSave` is pressed
+///
+/// Read in save::save_game
+///   - writes the current world in the slot
+#[derive(Event)]
+pub struct SaveEvent {
+    pub slot: usize,
+}
+
+/// Happens when:
+///   - save::quick_load
+///     - `Action::QuickLoad` is pressed
+///
+/// Read in save::load_game
+///   - reads the slot and starts the game, if in the menu
+#[derive(Event)]
+pub struct LoadEvent {
+    pub slot: usize,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+pub fn quick_save(action_input: ActionInput, mut save_event: EventWriter<SaveEvent>) {
+    if action_input.just_pressed(Action::QuickSave) {
+        save_event.send(SaveEvent {
+            slot: QUICK_SAVE_SLOT,
+        });
+    }
+}
+
+pub fn quick_load(action_input: ActionInput, mut load_event: EventWriter<LoadEvent>) {
+    if action_input.just_pressed(Action::QuickLoad) {
+        load_event.send(LoadEvent {
+            slot: QUICK_SAVE_SLOT,
+        });
+    }
+}
+
+/// Opening and Closing doors are saved as they will end.
+fn settled(door_state: DoorState) -> DoorState {
+    match door_state {
+        DoorState::Opened | DoorState::Opening => DoorState::Opened,
+        DoorState::Closed | DoorState::Closing => DoorState::Closed,
+    }
+}
+
+fn saved_character(transform: &Transform, location: &Location) -> SavedCharacter {
+    SavedCharacter {
+        position: (transform.translation.x, transform.translation.y),
+        location: *location,
+    }
+}
+
+/// Everything a `SaveGame` is made of.
+#[derive(SystemParam)]
+pub struct WorldSnapshot<'w, 's> {
+    player_query: Query<'w, 's, (&'static Transform, &'static Location), With<Player>>,
+    npc_query: Query<
+        'w,
+        's,
+        (
+            &'static Name,
+            &'static Transform,
+            &'static Location,
+            &'static NPCBehavior,
+            &'static Reputation,
+            Option<&'static Recruted>,
+        ),
+        With<NPC>,
+    >,
+    name_query: Query<'w, 's, &'static Name>,
+    landmark_query: Query<'w, 's, &'static Landmark>,
+
+    dialogs: Res<'w, DialogMap>,
+    active_world_events: Res<'w, ActiveWorldEvents>,
+    temple_door_query: Query<'w, 's, &'static DoorState, With<TempleDoor>>,
+    secret_banner_query: Query<'w, 's, &'static DoorState, With<SecretBanner>>,
+    cover_query: Query<'w, 's, &'static Sprite, With<SecretRoomCover>>,
+}
+
+impl WorldSnapshot<'_, '_> {
+    /// `None` without player.
+    pub fn take(&self) -> Option<SaveGame> {
+        let (player_transform, player_location) = self.player_query.get_single().ok()?;
+
+        let mut npcs = Vec::new();
+        let mut party = Vec::new();
+        for (name, transform, location, behavior, reputation, potential_recruted) in &self.npc_query
+        {
+            let behavior = match behavior {
+                NPCBehavior::Camping => SavedBehavior::Camping,
+                NPCBehavior::LandmarkSeeking(landmark, location) => {
+                    match self.landmark_query.get(*landmark) {
+                        Ok(landmark) => SavedBehavior::LandmarkSeeking {
+                            landmark: landmark.name.clone(),
+                            location: *location,
+                        },
+                        Err(_) => SavedBehavior::Camping,
+                    }
+                }
+                NPCBehavior::Follow { target, .. } => match self.name_query.get(*target) {
+                    Ok(target_name) => SavedBehavior::Follow {
+                        target: target_name.to_string(),
+                    },
+                    Err(_) => SavedBehavior::Camping,
+                },
+            };
+            if potential_recruted.is_some() {
+                party.push(name.to_string());
+            }
+            npcs.push(SavedNPC {
+                name: name.to_string(),
+                character: saved_character(transform, location),
+                behavior,
+                reputation: *reputation,
+            });
+        }
+
+        Some(SaveGame {
+            version: SAVE_VERSION,
+            player: saved_character(player_transform, player_location),
+            npcs,
+            dialogs: self
+                .dialogs
+                .iter()
+                .filter_map(|(interlocutor, (current_state, _))| {
+                    self.name_query
+                        .get(*interlocutor)
+                        .ok()
+                        .map(|name| (name.to_string(), *current_state))
+                })
+                .collect(),
+            world_events: self.active_world_events.to_vec(),
+            temple_door: self
+                .temple_door_query
+                .get_single()
+                .map_or(DoorState::Closed, |door_state| settled(*door_state)),
+            secret_banner: self
+                .secret_banner_query
+                .get_single()
+                .map_or(DoorState::Closed, |door_state| settled(*door_state)),
+            secret_room_cover_removed: self
+                .cover_query
+                .get_single()
+                .map_or(false, |sprite| sprite.color.a() < 0.5),
+            party,
+        })
+    }
+}
+
+pub fn save_game(mut save_event: EventReader<SaveEvent>, world_snapshot: WorldSnapshot) {
+    for SaveEvent { slot } in save_event.iter() {
+        let Some(save) = world_snapshot.take() else {
+            warn!("No player to save");
+            continue;
+        };
+        match write_save(*slot, &save) {
+            Ok(()) => info!("Game saved in slot {slot}"),
+            Err(error) => error!("Could not save in slot {slot}: {error:?}"),
+        }
+    }
+}
+
+/// The world is restored once in `GameState::Playing`.
+pub fn load_game(
+    mut commands: Commands,
+    mut load_event: EventReader<LoadEvent>,
+    game_state: Res<State<GameState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    for LoadEvent { slot } in load_event.iter() {
+        match read_save(*slot) {
+            Ok(save) => {
+                info!("Loading slot {slot}");
+                commands.insert_resource(PendingLoad(save));
+                if game_state.get() != &GameState::Playing {
+                    next_game_state.set(GameState::Playing);
+                }
+            }
+            Err(error) => error!("Could not load slot {slot}: {error:?}"),
+        }
+    }
+}
+
+/// Doors are moved by the usual animation, only faster.
+fn restore_door(
+    door: Entity,
+    current: DoorState,
+    saved: DoorState,
+    door_interact_event: &mut EventWriter<DoorInteractEvent>,
+) {
+    if settled(current) != settled(saved) {
+        door_interact_event.send(DoorInteractEvent {
+            door_entity: door,
+            open_delta_s: RESTORED_DOOR_DELTA_S,
+        });
+    }
+}
+
+pub fn apply_pending_load(
+    mut commands: Commands,
+    pending_load: Res<PendingLoad>,
+
+    mut player_query: Query<(Entity, &mut Transform, &mut Location), With<Player>>,
+    mut npc_query: Query<
+        (
+            Entity,
+            &Name,
+            &mut Transform,
+            &mut Location,
+            &mut NPCBehavior,
+            &mut Reputation,
+        ),
+        (With<NPC>, Without<Player>),
+    >,
+    name_query: Query<(Entity, &Name)>,
+    mut landmark_sensor_query: Query<(Entity, &mut Landmark), With<Sensor>>,
+
+    mut dialogs: ResMut<DialogMap>,
+    mut active_world_events: ResMut<ActiveWorldEvents>,
+    temple_door_query: Query<(Entity, &DoorState), With<TempleDoor>>,
+    secret_banner_query: Query<(Entity, &DoorState), With<SecretBanner>>,
+    cover_query: Query<&Sprite, With<SecretRoomCover>>,
+
+    mut door_interact_event: EventWriter<DoorInteractEvent>,
+    mut remove_cover_event: EventWriter<RemoveSecretRoomCoverEvent>,
+    mut add_cover_event: EventWriter<AddSecretRoomCoverEvent>,
+) {
+    let save = &pending_load.0;
+    commands.remove_resource::<PendingLoad>();
+
+    let find_entity = |name: &str| {
+        name_query
+            .iter()
+            .find(|(_, entity_name)| entity_name.as_str() == name)
+            .map(|(entity, _)| entity)
+    };
+
+    if let Ok((_, mut transform, mut location)) = player_query.get_single_mut() {
+        (transform.translation.x, transform.translation.y) = save.player.position;
+        *location = save.player.location;
+    }
+
+    // Every npc gets its landmark back
+    for (_, mut landmark) in &mut landmark_sensor_query {
+        landmark.status = LandmarkStatus::Free;
+    }
+
+    for saved_npc in &save.npcs {
+        let Some((npc, _, mut transform, mut location, mut behavior, mut reputation)) = npc_query
+            .iter_mut()
+            .find(|(_, name, ..)| name.as_str() == saved_npc.name)
+        else {
+            warn!("Saved npc {} is not in the world", saved_npc.name);
+            continue;
+        };
+
+        (transform.translation.x, transform.translation.y) = saved_npc.character.position;
+        *location = saved_npc.character.location;
+        *reputation = saved_npc.reputation;
+        release_reservation(&mut landmark_sensor_query, npc);
+        *behavior = match &saved_npc.behavior {
+            SavedBehavior::Camping => NPCBehavior::Camping,
+            SavedBehavior::LandmarkSeeking { landmark, location } => seek_requested_landmark(
+                &mut landmark_sensor_query,
+                *location,
+                &LandmarkRequest::Named(landmark.clone()),
+                npc,
+            ),
+            SavedBehavior::Follow { target } => match find_entity(target) {
+                Some(target) => NPCBehavior::follow(target, false),
+                None => NPCBehavior::Camping,
+            },
+        };
+
+        if save.party.contains(&saved_npc.name) {
+            commands.entity(npc).insert(Recruted);
+        } else {
+            commands.entity(npc).remove::<Recruted>();
+        }
+    }
+
+    for (interlocutor_name, current_state) in &save.dialogs {
+        let Some(interlocutor) = find_entity(interlocutor_name) else {
+            continue;
+        };
+        if let Some((state, _)) = dialogs.get_mut(&interlocutor) {
+            *state = *current_state;
+        }
+    }
+
+    active_world_events.clear();
+    active_world_events.extend(save.world_events.iter().copied());
+
+    if let Ok((temple_door, door_state)) = temple_door_query.get_single() {
+        restore_door(
+            temple_door,
+            *door_state,
+            save.temple_door,
+            &mut door_interact_event,
+        );
+    }
+    if let Ok((secret_banner, door_state)) = secret_banner_query.get_single() {
+        restore_door(
+            secret_banner,
+            *door_state,
+            save.secret_banner,
+            &mut door_interact_event,
+        );
+    }
+
+    if let Ok(sprite) = cover_query.get_single() {
+        let cover_removed = sprite.color.a() < 0.5;
+        if save.secret_room_cover_removed && !cover_removed {
+            remove_cover_event.send(RemoveSecretRoomCoverEvent);
+        } else if !save.secret_room_cover_removed && cover_removed {
+            add_cover_event.send(AddSecretRoomCoverEvent);
+        }
+    }
+
+    info!("Save v{} restored", save.version);
+}
@@ -4,7 +4,7 @@
 //!
 //! - Enter in Combat
 //! - Exit in Combat
-//! - Open HUD manually (pressing 'o')
+//! - Open HUD manually (`Action::ToggleHUD`, 'o' by default)
 //! - Scolls Gestion
 //!   - Update Dialog Tree
 //!   - Update each Scroll
@@ -17,6 +17,7 @@ use std::time::Duration;
 use crate::{
     characters::player::Player,
     constants::ui::dialogs::*,
+    controls::{Action, ActionInput},
     ui::dialog_scrolls::{ButtonChoice, MonologPanel, PlayerChoicePanel, Scroll, ScrollTimer},
     HUDState,
 };
@@ -72,7 +73,7 @@ pub fn load_textures(
 ///
 /// FIXME: PB Spamming the ui key 'o'; ?throws an error
 pub fn create_dialog_panel_on_key_press(
-    keyboard_input: Res<Input<KeyCode>>,
+    action_input: ActionInput,
     query: Query<(Entity, &Animator<Style>, &Style), With<DialogPanel>>,
 
     mut current_interlocutor: ResMut<CurrentInterlocutor>,
@@ -80,7 +81,7 @@ pub fn create_dialog_panel_on_key_press(
 
     mut next_game_state: ResMut<NextState<HUDState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::O) {
+    if action_input.just_pressed(Action::ToggleHUD) {
         if let Ok((_entity, animator, _style)) = query.get_single() {
             if animator.tweenable().progress() >= 1. {
                 next_game_state.set(HUDState::Closed);
@@ -1,16 +1,22 @@
 //! All dialog method handler related with the player directly (input, etc)
 
-use bevy::{
-    input::{keyboard::KeyboardInput, ButtonState},
-    prelude::*,
-};
+use bevy::prelude::*;
+use strum::IntoEnumIterator;
 use yml_dialog::Content;
 
-use crate::ui::{
-    dialog_scrolls::{ButtonChoice, Monolog},
-    dialog_systems::{ChangeStateEvent, CurrentInterlocutor, DialogMap},
+use crate::{
+    constants::ui::dialogs::{HOVERED_BUTTON, NORMAL_BUTTON},
+    controls::{Action, ActionInput},
+    ui::{
+        dialog_scrolls::{ButtonChoice, Monolog},
+        dialog_systems::{ChangeStateEvent, CurrentInterlocutor, DialogMap},
+    },
 };
 
+/// The `ui_position` of the choice selected without the mouse (d-pad, arrows).
+#[derive(Default, Resource)]
+pub struct FocusedChoice(pub Option<usize>);
+
 pub fn choose_answer(
     choice_query: Query<(&ButtonChoice, &Interaction), Changed<Interaction>>,
     mut change_state_event: EventWriter<ChangeStateEvent>,
@@ -22,39 +28,118 @@ pub fn choose_answer(
     }
 }
 
+/// Moves the focus between the visible choices with `Up` and `Down`,
+/// or to the one hovered by the mouse.
+/// The first one is focused when new choices appear.
+pub fn focus_choices(
+    action_input: ActionInput,
+    mut focused_choice: ResMut<FocusedChoice>,
+    choice_query: Query<(&ButtonChoice, &Visibility)>,
+    hovered_choice_query: Query<(&ButtonChoice, &Interaction), Changed<Interaction>>,
+) {
+    for (button_infos, interaction) in &hovered_choice_query {
+        if *interaction == Interaction::Hovered {
+            focused_choice.0 = Some(button_infos.ui_position);
+        }
+    }
+
+    let mut visible_choices: Vec<usize> = choice_query
+        .iter()
+        .filter(|(_, visibility)| **visibility != Visibility::Hidden)
+        .map(|(button_infos, _)| button_infos.ui_position)
+        .collect();
+    visible_choices.sort();
+
+    let Some(current) = focused_choice.0.and_then(|focused| {
+        visible_choices
+            .iter()
+            .position(|position| *position == focused)
+    }) else {
+        let first = visible_choices.first().copied();
+        if focused_choice.0 != first {
+            focused_choice.0 = first;
+        }
+        return;
+    };
+
+    let count = visible_choices.len();
+    if action_input.just_pressed(Action::Down) {
+        focused_choice.0 = Some(visible_choices[(current + 1) % count]);
+    } else if action_input.just_pressed(Action::Up) {
+        focused_choice.0 = Some(visible_choices[(current + count - 1) % count]);
+    }
+}
+
+pub fn highlight_focused_choice(
+    focused_choice: Res<FocusedChoice>,
+    mut choice_query: Query<(&ButtonChoice, &Interaction, &mut BackgroundColor)>,
+) {
+    for (button_infos, interaction, mut background_color) in &mut choice_query {
+        let color = if focused_choice.0 == Some(button_infos.ui_position)
+            || *interaction == Interaction::Hovered
+        {
+            HOVERED_BUTTON
+        } else {
+            NORMAL_BUTTON
+        };
+        if background_color.0 != color {
+            background_color.0 = color;
+        }
+    }
+}
+
+/// `Interact` chooses the focused answer;
+/// `Choice1`, `Choice2` and `Choice3` (1, 2, 3 by default) choose directly.
+pub fn confirm_focused_choice(
+    action_input: ActionInput,
+    focused_choice: Res<FocusedChoice>,
+    choice_query: Query<(&ButtonChoice, &Visibility)>,
+    mut change_state_event: EventWriter<ChangeStateEvent>,
+) {
+    let chosen = if action_input.just_pressed(Action::Interact) {
+        focused_choice.0
+    } else {
+        Action::iter()
+            .filter(|action| action_input.just_pressed(*action))
+            .find_map(|action| action.choice_position())
+    };
+    let Some(chosen) = chosen else {
+        return;
+    };
+    if let Some((button_infos, _)) = choice_query.iter().find(|(button_infos, visibility)| {
+        button_infos.ui_position == chosen && **visibility != Visibility::Hidden
+    }) {
+        change_state_event.send(ChangeStateEvent(button_infos.exit_state));
+    }
+}
+
+/// Only `Interact` says the next line:
+/// moving the focus, or any other key, must not skip lines by accident.
 pub fn continue_monolog(
-    mut key_evr: EventReader<KeyboardInput>,
+    action_input: ActionInput,
     mut current_monolog: ResMut<Monolog>,
     current_interlocutor: Res<CurrentInterlocutor>,
     dialogs: Res<DialogMap>,
 
     mut change_state_event: EventWriter<ChangeStateEvent>,
 ) {
-    for KeyboardInput {
-        scan_code: _,
-        key_code: _,
-        state,
-        window: _,
-    } in key_evr.iter()
-    {
-        if *state == ButtonState::Pressed {
-            if current_monolog.texts.len() > 1 {
-                if let Some((_first, rem)) = current_monolog.texts.split_first() {
-                    current_monolog.texts = rem.to_vec();
-                }
-            } else {
-                match current_interlocutor.interlocutor {
-                    None => {}
-                    Some(interlocutor) => {
-                        if let Some(&(current_state, ref dialog)) = dialogs.get(&interlocutor) {
-                            if let Some(current_node) = dialog.get(&current_state) {
-                                match current_node.content() {
-                                    Content::Choices(_) => {}
-                                    Content::Monolog {
-                                        text: _,
-                                        exit_state,
-                                    } => change_state_event.send(ChangeStateEvent(*exit_state)),
-                                }
+    if action_input.just_pressed(Action::Interact) {
+        if current_monolog.texts.len() > 1 {
+            if let Some((_first, rem)) = current_monolog.texts.split_first() {
+                current_monolog.texts = rem.to_vec();
+            }
+        } else {
+            match current_interlocutor.interlocutor {
+                None => {}
+                Some(interlocutor) => {
+                    if let Some(&(current_state, ref dialog)) = dialogs.get(&interlocutor) {
+                        if let Some(current_node) = dialog.get(&current_state) {
+                            match current_node.content() {
+                                Content::Choices(_) => {}
+                                Content::Monolog {
+                                    text: _,
+                                    exit_state,
+                                } => change_state_event.send(ChangeStateEvent(*exit_state)),
                             }
                         }
                     }
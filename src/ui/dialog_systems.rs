@@ -5,15 +5,20 @@ use std::{collections::BTreeMap, str::FromStr};
 
 use bevy::prelude::*;
 use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
 use yml_dialog::{Content, DialogNode};
 
 use crate::{
-    characters::{npcs::movement::FollowEvent, player::Player},
+    characters::{
+        npcs::{memory::Memory, movement::FollowEvent},
+        player::Player,
+    },
     HUDState,
 };
 
 use super::{
     dialog_box::ResetDialogBoxEvent,
+    dialog_player::FocusedChoice,
     dialog_scrolls::{ButtonChoice, Monolog, MonologPanel},
 };
 
@@ -44,7 +49,7 @@ pub struct ActiveWorldEvents {
 #[derive(Debug, Deref, DerefMut, Default, Resource)]
 pub struct DialogMap(BTreeMap<Entity, (usize, BTreeMap<usize, DialogNode>)>);
 
-#[derive(Reflect, PartialEq, Clone, Copy, Debug)]
+#[derive(Serialize, Deserialize, Reflect, PartialEq, Clone, Copy, Debug)]
 pub enum WorldEvent {
     BeatTheGame,
     FirstKill,
@@ -91,10 +96,40 @@ impl FromStr for WorldEvent {
     }
 }
 
+/// Events checked by the dialog conditions:
+/// the active world events and the memories of the interlocutor.
+fn condition_events(
+    active_world_events: &ActiveWorldEvents,
+    potential_memory: Option<&Memory>,
+) -> Vec<String> {
+    let mut events: Vec<String> = active_world_events.iter().map(|x| x.to_string()).collect();
+    if let Some(memory) = potential_memory {
+        events.extend(memory.dialog_events());
+    }
+    events
+}
+
 /* -------------------------------------------------------------------------- */
 /*                                   Systems                                  */
 /* -------------------------------------------------------------------------- */
 
+/// Occurs `OnExit(GameState::Playing)`
+///
+/// The interlocutors are despawned: a new game starts with fresh dialogs and no world event.
+pub fn reset_dialogs(
+    mut dialogs: ResMut<DialogMap>,
+    mut current_interlocutor: ResMut<CurrentInterlocutor>,
+    mut active_world_events: ResMut<ActiveWorldEvents>,
+    mut monolog: ResMut<Monolog>,
+    mut focused_choice: ResMut<FocusedChoice>,
+) {
+    dialogs.clear();
+    *current_interlocutor = CurrentInterlocutor::default();
+    *active_world_events = ActiveWorldEvents::default();
+    *monolog = Monolog::default();
+    *focused_choice = FocusedChoice::default();
+}
+
 /// Happens when
 ///   - `dialog_dive()`
 ///     - when leaving a node
@@ -165,12 +200,15 @@ pub fn change_dialog_state(
     current_interlocutor: Res<CurrentInterlocutor>,
     mut dialogs: ResMut<DialogMap>,
     active_world_events: Res<ActiveWorldEvents>,
+    memory_query: Query<&Memory>,
 
     mut next_game_state: ResMut<NextState<HUDState>>,
     mut trigger_event: EventWriter<TriggerEvents>,
 ) {
     for ChangeStateEvent(new_state) in change_state_event.iter() {
         if let Some(interlocutor) = current_interlocutor.interlocutor {
+            let events =
+                condition_events(&active_world_events, memory_query.get(interlocutor).ok());
             if let Some((current_state, ref dialog)) = dialogs.get_mut(&interlocutor) {
                 match dialog.get(new_state) {
                     None => next_game_state.set(HUDState::Closed),
@@ -180,13 +218,7 @@ pub fn change_dialog_state(
                             Content::Choices(choices) => {
                                 let mut at_least_one_is_verified = false;
                                 for choice in choices {
-                                    if choice.is_verified(
-                                        None,
-                                        active_world_events
-                                            .iter()
-                                            .map(|x| x.to_string())
-                                            .collect::<Vec<String>>(),
-                                    ) {
+                                    if choice.is_verified(None, events.clone()) {
                                         // transit if at least on verified
                                         at_least_one_is_verified = true;
                                         break;
@@ -227,6 +259,7 @@ pub fn update_dialog_panel(
     current_interlocutor: Res<CurrentInterlocutor>,
     active_world_events: Res<ActiveWorldEvents>,
     dialogs: Res<DialogMap>,
+    memory_query: Query<&Memory>,
 
     mut current_monolog: ResMut<Monolog>,
     mut player_choices_query: Query<(Entity, &mut ButtonChoice, &mut Visibility)>,
@@ -238,6 +271,7 @@ pub fn update_dialog_panel(
     {
         // info!("UpdateDialogPanel");
         let interlocutor = current_interlocutor.interlocutor.unwrap();
+        let events = condition_events(&active_world_events, memory_query.get(interlocutor).ok());
         if let Some(&(current_state, ref dialog)) = dialogs.get(&interlocutor) {
             // info!("current_state: {}", current_state);
             match dialog.get(&current_state) {
@@ -268,13 +302,7 @@ pub fn update_dialog_panel(
                                 let mut verified_choices = Vec::<(usize, String)>::new();
 
                                 for choice in choices.iter() {
-                                    if choice.is_verified(
-                                        None,
-                                        active_world_events
-                                            .iter()
-                                            .map(|x| x.to_string())
-                                            .collect::<Vec<String>>(),
-                                    ) {
+                                    if choice.is_verified(None, events.clone()) {
                                         // info!(
                                         //     "{} -> {}",
                                         //     choice.text().to_owned(),
@@ -311,23 +339,22 @@ pub fn update_dialog_panel(
                                 // }
                             } else {
                                 // NPC Choices
+                                // A verified condition (e.g. a memory) prevails over the unconditional choices
                                 let mut possible_choices_index: Vec<usize> = Vec::new();
+                                let mut verified_choices_index: Vec<usize> = Vec::new();
                                 for choice in choices.iter() {
                                     match choice.condition() {
                                         None => possible_choices_index.push(*choice.exit_state()),
                                         Some(condition) => {
-                                            if condition.is_verified(
-                                                None,
-                                                active_world_events
-                                                    .iter()
-                                                    .map(|x| x.to_string())
-                                                    .collect::<Vec<String>>(),
-                                            ) {
-                                                possible_choices_index.push(*choice.exit_state());
+                                            if condition.is_verified(None, events.clone()) {
+                                                verified_choices_index.push(*choice.exit_state());
                                             }
                                         }
                                     }
                                 }
+                                if !verified_choices_index.is_empty() {
+                                    possible_choices_index = verified_choices_index;
+                                }
                                 if let Some(child_index) =
                                     possible_choices_index.choose(&mut rand::thread_rng())
                                 {
@@ -1,12 +1,13 @@
 use bevy::{prelude::*, winit::WinitSettings};
 
-use crate::HUDState;
+use crate::{paused, GameState, HUDState};
 
 mod dialog_box;
 pub mod dialog_panel;
 mod dialog_player;
 pub mod dialog_scrolls;
 pub mod dialog_systems;
+pub mod options;
 
 pub struct UiPlugin;
 
@@ -20,6 +21,9 @@ impl Plugin for UiPlugin {
             .insert_resource(dialog_systems::CurrentInterlocutor::default())
             .insert_resource(dialog_systems::ActiveWorldEvents::default())
             .insert_resource(dialog_scrolls::Monolog::default())
+            .init_resource::<dialog_player::FocusedChoice>()
+            .init_resource::<options::Rebinding>()
+            .init_resource::<options::OptionsResources>()
             .add_event::<dialog_box::ResetDialogBoxEvent>()
             .add_event::<dialog_systems::ChangeStateEvent>()
             .add_event::<dialog_systems::TriggerEvents>()
@@ -31,7 +35,9 @@ impl Plugin for UiPlugin {
             .add_systems(
                 Update,
                 (
-                    dialog_panel::create_dialog_panel_on_key_press,
+                    dialog_panel::create_dialog_panel_on_key_press
+                        .run_if(not(in_state(HUDState::OptionsWall)))
+                        .run_if(not(paused)),
                     dialog_panel::despawn_dialog_panel,
                 ),
             )
@@ -54,6 +60,9 @@ impl Plugin for UiPlugin {
                     /* -------------------------------------------------------------------------- */
                     dialog_player::choose_answer,
                     dialog_player::continue_monolog,
+                    dialog_player::focus_choices,
+                    dialog_player::highlight_focused_choice.after(dialog_player::focus_choices),
+                    dialog_player::confirm_focused_choice.after(dialog_player::focus_choices),
                     /* -------------------------------------------------------------------------- */
                     /*                                  Animation                                 */
                     /* -------------------------------------------------------------------------- */
@@ -66,7 +75,37 @@ impl Plugin for UiPlugin {
             .add_systems(
                 OnExit(HUDState::DialogWall),
                 dialog_panel::close_dialog_panel,
-            );
+            )
+            .add_systems(
+                Update,
+                options::toggle_options_on_key_press
+                    .before(options::rebind_key)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                OnEnter(HUDState::OptionsWall),
+                options::create_options_panel,
+            )
+            .add_systems(
+                Update,
+                (
+                    options::key_binding_buttons,
+                    options::restore_default_bindings,
+                    options::rebind_key.after(options::key_binding_buttons),
+                    options::refresh_key_binding_rows
+                        .after(options::rebind_key)
+                        .after(options::restore_default_bindings),
+                    options::update_key_binding_buttons
+                        .after(options::rebind_key)
+                        .after(options::restore_default_bindings),
+                    options::language_button,
+                    options::volume_buttons,
+                    options::update_option_buttons,
+                )
+                    .run_if(in_state(HUDState::OptionsWall)),
+            )
+            .add_systems(OnExit(HUDState::OptionsWall), options::close_options_panel)
+            .add_systems(OnExit(GameState::Playing), dialog_systems::reset_dialogs);
     }
 }
 
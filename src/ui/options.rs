@@ -0,0 +1,540 @@
+//! Options panel, on `HUDState::OptionsWall`
+//!
+//! Lists each `Action` with its keys.
+//! Clicking a key waits for the next key pressed to bind it instead
+//! (Backspace unbinds it); clicking "+" binds one more key to the action.
+//! A key already bound elsewhere is refused (conflict).
+//! Changes apply right away and are saved by `controls::save_key_bindings`.
+//!
+//! Below the controls: the language (cycled by its button) and the music volume.
+
+use bevy::prelude::*;
+use strum::IntoEnumIterator;
+
+use crate::{
+    constants::ui::options::*,
+    controls::{Action, Key, KeyBindings},
+    dialogs::Language,
+    HUDState, MusicVolume, PauseState,
+};
+
+/* -------------------------------------------------------------------------- */
+/*                                 Components                                 */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Component)]
+pub struct OptionsPanel;
+
+/// The `index`-th key of the `action`;
+/// the one past the last key adds a new key.
+#[derive(Component)]
+pub struct KeyBindingButton {
+    pub action: Action,
+    pub index: usize,
+}
+
+/// Rebuilt each time the `KeyBindings` change.
+#[derive(Component)]
+pub struct KeyBindingRow(pub Action);
+
+#[derive(Component)]
+pub struct RestoreDefaultsButton;
+
+/// Switches to the next `Language`.
+#[derive(Component)]
+pub struct LanguageButton;
+
+/// Adds this step to the `MusicVolume`.
+#[derive(Component)]
+pub struct VolumeButton(pub f32);
+
+#[derive(Component)]
+pub struct VolumeText;
+
+/// Shows the pending rebinding, the conflicts, ...
+#[derive(Component)]
+pub struct OptionsStatus;
+
+/// The key slot waiting for a new key, if any.
+#[derive(Default, Resource)]
+pub struct Rebinding(pub Option<(Action, usize)>);
+
+#[derive(Resource)]
+pub struct OptionsResources {
+    font: Handle<Font>,
+}
+
+impl OptionsResources {
+    pub fn text_style(&self) -> TextStyle {
+        TextStyle {
+            font: self.font.clone(),
+            font_size: OPTIONS_FONT_SIZE,
+            color: Color::WHITE,
+        }
+    }
+}
+
+impl FromWorld for OptionsResources {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        OptionsResources {
+            font: asset_server.load("fonts/dpcomic.ttf"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Systems                                  */
+/* -------------------------------------------------------------------------- */
+
+/// Opens or closes the options, if no other hud is opened.
+///
+/// The game is paused first: the options are opened above the pause menu,
+/// which stays once they are closed.
+pub fn toggle_options_on_key_press(
+    keyboard_input: Res<Input<KeyCode>>,
+    rebinding: Res<Rebinding>,
+    hud_state: Res<State<HUDState>>,
+    mut next_hud_state: ResMut<NextState<HUDState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    if !keyboard_input.just_pressed(OPTIONS_KEY) || rebinding.0.is_some() {
+        return;
+    }
+    match hud_state.get() {
+        HUDState::Closed => {
+            next_pause_state.set(PauseState::Paused);
+            next_hud_state.set(HUDState::OptionsWall);
+        }
+        HUDState::OptionsWall => next_hud_state.set(HUDState::Closed),
+        _ => {}
+    }
+}
+
+pub fn create_options_panel(
+    mut commands: Commands,
+    options_resources: Res<OptionsResources>,
+    key_bindings: Res<KeyBindings>,
+    language: Res<Language>,
+    music_volume: Res<MusicVolume>,
+) {
+    let text_style = options_resources.text_style();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(OPTIONS_ROW_GAP),
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: OPTIONS_BACKGROUND.into(),
+                // Above the pause menu
+                z_index: ZIndex::Global(1),
+                ..default()
+            },
+            OptionsPanel,
+            Name::new("Options Panel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Controls",
+                TextStyle {
+                    font_size: OPTIONS_TITLE_FONT_SIZE,
+                    ..text_style.clone()
+                },
+            ));
+
+            for action in Action::iter() {
+                parent
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Row,
+                                align_items: AlignItems::Center,
+                                column_gap: Val::Px(OPTIONS_ROW_GAP),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        KeyBindingRow(action),
+                        Name::new(format!("{action} Bindings")),
+                    ))
+                    .with_children(|row| {
+                        spawn_key_binding_row(row, action, &key_bindings, &text_style);
+                    });
+            }
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            padding: UiRect::all(Val::Px(OPTIONS_ROW_GAP / 2.)),
+                            ..default()
+                        },
+                        background_color: OPTIONS_NORMAL_BUTTON.into(),
+                        ..default()
+                    },
+                    RestoreDefaultsButton,
+                ))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section(
+                        "Restore defaults",
+                        text_style.clone(),
+                    ));
+                });
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(OPTIONS_ROW_GAP),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    spawn_option_label(row, "Language", &text_style);
+                    spawn_option_button(
+                        row,
+                        LanguageButton,
+                        language.to_string(),
+                        OPTIONS_KEY_WIDTH,
+                        &text_style,
+                    );
+                });
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(OPTIONS_ROW_GAP),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    spawn_option_label(row, "Music", &text_style);
+                    spawn_option_button(
+                        row,
+                        VolumeButton(-OPTIONS_VOLUME_STEP),
+                        "-".to_string(),
+                        OPTIONS_ADD_KEY_WIDTH,
+                        &text_style,
+                    );
+                    row.spawn((
+                        TextBundle::from_section(volume_label(&music_volume), text_style.clone()),
+                        VolumeText,
+                    ));
+                    spawn_option_button(
+                        row,
+                        VolumeButton(OPTIONS_VOLUME_STEP),
+                        "+".to_string(),
+                        OPTIONS_ADD_KEY_WIDTH,
+                        &text_style,
+                    );
+                });
+
+            parent.spawn((
+                TextBundle::from_section(
+                    format!("{:?} to close", OPTIONS_KEY),
+                    TextStyle {
+                        color: OPTIONS_STATUS_COLOR,
+                        ..text_style.clone()
+                    },
+                ),
+                OptionsStatus,
+            ));
+        });
+}
+
+fn spawn_option_label(row: &mut ChildBuilder, label: &str, text_style: &TextStyle) {
+    row.spawn(
+        TextBundle::from_section(label, text_style.clone()).with_style(Style {
+            width: Val::Px(OPTIONS_ACTION_WIDTH),
+            ..default()
+        }),
+    );
+}
+
+fn spawn_option_button(
+    row: &mut ChildBuilder,
+    button: impl Component,
+    label: String,
+    width: f32,
+    text_style: &TextStyle,
+) {
+    row.spawn((
+        ButtonBundle {
+            style: Style {
+                width: Val::Px(width),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            background_color: OPTIONS_NORMAL_BUTTON.into(),
+            ..default()
+        },
+        button,
+    ))
+    .with_children(|button| {
+        button.spawn(TextBundle::from_section(label, text_style.clone()));
+    });
+}
+
+fn volume_label(music_volume: &MusicVolume) -> String {
+    format!("{:.0}%", music_volume.0 * 100.)
+}
+
+/// The action name, its keys and the "+" button.
+fn spawn_key_binding_row(
+    row: &mut ChildBuilder,
+    action: Action,
+    key_bindings: &KeyBindings,
+    text_style: &TextStyle,
+) {
+    spawn_option_label(row, &action.to_string(), text_style);
+
+    let keys = key_bindings.keys(action);
+    let labels = keys
+        .iter()
+        .map(|key| format!("{:?}", key.0))
+        .chain(std::iter::once("+".to_string()));
+    for (index, label) in labels.enumerate() {
+        let width = if index < keys.len() {
+            OPTIONS_KEY_WIDTH
+        } else {
+            OPTIONS_ADD_KEY_WIDTH
+        };
+        row.spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(width),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: OPTIONS_NORMAL_BUTTON.into(),
+                ..default()
+            },
+            KeyBindingButton { action, index },
+        ))
+        .with_children(|button| {
+            button.spawn(TextBundle::from_section(label, text_style.clone()));
+        });
+    }
+}
+
+/// The number of keys of an action can change: its whole row is rebuilt.
+pub fn refresh_key_binding_rows(
+    mut commands: Commands,
+    options_resources: Res<OptionsResources>,
+    key_bindings: Res<KeyBindings>,
+    row_query: Query<(Entity, &KeyBindingRow)>,
+) {
+    if !key_bindings.is_changed() {
+        return;
+    }
+    let text_style = options_resources.text_style();
+    for (row, KeyBindingRow(action)) in &row_query {
+        commands
+            .entity(row)
+            .despawn_descendants()
+            .with_children(|row| {
+                spawn_key_binding_row(row, *action, &key_bindings, &text_style);
+            });
+    }
+}
+
+pub fn close_options_panel(
+    mut commands: Commands,
+    mut rebinding: ResMut<Rebinding>,
+    panel_query: Query<Entity, With<OptionsPanel>>,
+) {
+    rebinding.0 = None;
+    for panel in &panel_query {
+        commands.entity(panel).despawn_recursive();
+    }
+}
+
+/// Clicking a key slot waits for the next key.
+pub fn key_binding_buttons(
+    mut rebinding: ResMut<Rebinding>,
+    button_query: Query<(&Interaction, &KeyBindingButton), Changed<Interaction>>,
+    mut status_query: Query<&mut Text, With<OptionsStatus>>,
+) {
+    for (interaction, KeyBindingButton { action, index }) in &button_query {
+        if *interaction == Interaction::Pressed {
+            rebinding.0 = Some((*action, *index));
+            if let Ok(mut status) = status_query.get_single_mut() {
+                status.sections[0].value = format!("Press a key for {action} (Escape to cancel)");
+            }
+        }
+    }
+}
+
+/// Binds the key pressed to the waiting slot, unless bound elsewhere.
+/// Backspace unbinds the slot, if the action keeps at least one key.
+pub fn rebind_key(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut key_bindings: ResMut<KeyBindings>,
+    mut status_query: Query<&mut Text, With<OptionsStatus>>,
+) {
+    let Some((action, index)) = rebinding.0 else {
+        return;
+    };
+    let Some(key_code) = keyboard_input.get_just_pressed().next().copied() else {
+        return;
+    };
+    let Ok(mut status) = status_query.get_single_mut() else {
+        return;
+    };
+
+    if key_code == KeyCode::Escape {
+        status.sections[0].value = "Rebinding cancelled".to_string();
+    } else if key_code == OPTIONS_KEY {
+        status.sections[0].value = format!("{key_code:?} is reserved to the options");
+    } else if key_code == KeyCode::Back {
+        let keys = key_bindings.keys(action);
+        if index >= keys.len() {
+            status.sections[0].value = "Rebinding cancelled".to_string();
+        } else if keys.len() == 1 {
+            status.sections[0].value = format!("{action} needs at least one key");
+        } else {
+            let key = key_bindings.keys_mut(action).remove(index);
+            status.sections[0].value = format!("{:?} unbound from {action}", key.0);
+        }
+    } else {
+        match key_bindings.bound_to(key_code) {
+            Some((bound_action, bound_index)) if (bound_action, bound_index) == (action, index) => {
+                status.sections[0].value = format!("{key_code:?} kept for {action}");
+            }
+            Some((bound_action, _)) => {
+                status.sections[0].value =
+                    format!("{key_code:?} is already bound to {bound_action}");
+            }
+            None => {
+                let keys = key_bindings.keys_mut(action);
+                match keys.get_mut(index) {
+                    Some(key) => key.0 = key_code,
+                    None => keys.push(Key(key_code)),
+                }
+                status.sections[0].value = format!("{key_code:?} bound to {action}");
+            }
+        }
+    }
+    rebinding.0 = None;
+}
+
+pub fn restore_default_bindings(
+    mut key_bindings: ResMut<KeyBindings>,
+    mut rebinding: ResMut<Rebinding>,
+    button_query: Query<&Interaction, (Changed<Interaction>, With<RestoreDefaultsButton>)>,
+    mut status_query: Query<&mut Text, With<OptionsStatus>>,
+) {
+    for interaction in &button_query {
+        if *interaction == Interaction::Pressed {
+            *key_bindings = KeyBindings::default();
+            rebinding.0 = None;
+            if let Ok(mut status) = status_query.get_single_mut() {
+                status.sections[0].value = "Default controls restored".to_string();
+            }
+        }
+    }
+}
+
+/// Highlights the slot waiting for a new key.
+pub fn update_key_binding_buttons(
+    key_bindings: Res<KeyBindings>,
+    rebinding: Res<Rebinding>,
+    mut button_query: Query<(
+        &KeyBindingButton,
+        &Interaction,
+        &mut BackgroundColor,
+        &Children,
+    )>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (KeyBindingButton { action, index }, interaction, mut color, children) in &mut button_query
+    {
+        let waiting = rebinding.0 == Some((*action, *index));
+        *color = match (waiting, interaction) {
+            (true, _) => OPTIONS_WAITING_BUTTON.into(),
+            (false, Interaction::Hovered) => OPTIONS_HOVERED_BUTTON.into(),
+            (false, _) => OPTIONS_NORMAL_BUTTON.into(),
+        };
+
+        if !rebinding.is_changed() {
+            continue;
+        }
+        for child in children {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = match (waiting, key_bindings.keys(*action).get(*index)) {
+                    (true, _) => "...".to_string(),
+                    (false, Some(key)) => format!("{:?}", key.0),
+                    (false, None) => "+".to_string(),
+                };
+            }
+        }
+    }
+}
+
+pub fn language_button(
+    mut language: ResMut<Language>,
+    mut button_query: Query<
+        (&Interaction, &Children),
+        (Changed<Interaction>, With<LanguageButton>),
+    >,
+    mut text_query: Query<&mut Text>,
+) {
+    for (interaction, children) in &mut button_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        *language = Language::iter()
+            .cycle()
+            .skip_while(|l| *l != *language)
+            .nth(1)
+            .unwrap_or_default();
+        for child in children {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = language.to_string();
+            }
+        }
+    }
+}
+
+pub fn volume_buttons(
+    mut music_volume: ResMut<MusicVolume>,
+    button_query: Query<(&Interaction, &VolumeButton), Changed<Interaction>>,
+    mut volume_text_query: Query<&mut Text, With<VolumeText>>,
+) {
+    for (interaction, VolumeButton(step)) in &button_query {
+        if *interaction == Interaction::Pressed {
+            music_volume.0 = (music_volume.0 + step).clamp(0., 1.);
+            if let Ok(mut text) = volume_text_query.get_single_mut() {
+                text.sections[0].value = volume_label(&music_volume);
+            }
+        }
+    }
+}
+
+pub fn update_option_buttons(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        Or<(With<LanguageButton>, With<VolumeButton>)>,
+    >,
+) {
+    for (interaction, mut color) in &mut button_query {
+        *color = match interaction {
+            Interaction::Hovered | Interaction::Pressed => OPTIONS_HOVERED_BUTTON.into(),
+            Interaction::None => OPTIONS_NORMAL_BUTTON.into(),
+        };
+    }
+}